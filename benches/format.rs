@@ -0,0 +1,36 @@
+//! Times `format` over a large vector of ints.
+//!
+//! Run with:
+//! ```bash
+//! cargo bench --bench format
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use vaca::bezerro::{apply, register_builtins, Env, Value};
+
+fn main() {
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    let format = env.borrow().get("format").expect("format builtin");
+
+    let items = Value::Vector((0..1_000_000).map(Value::Int).collect());
+    let runs = 10;
+
+    let start = Instant::now();
+    let mut len = 0;
+    for _ in 0..runs {
+        let Ok(Value::String(s)) = apply(&format, std::slice::from_ref(&items), &env) else {
+            panic!("format should return a string");
+        };
+        len = s.len();
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "format over 1M ints: {:?}/run ({len} bytes)",
+        elapsed / runs
+    );
+}
@@ -0,0 +1,43 @@
+//! Times reading and evaluating a program with thousands of symbol and keyword references.
+//!
+//! Run with:
+//! ```bash
+//! cargo bench --bench symbols
+//! ```
+
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::Rc;
+use std::time::Instant;
+
+use vaca::bezerro::{eval, register_builtins, Env, Value};
+
+fn main() {
+    let mut src = String::new();
+    for i in 0..2_000 {
+        writeln!(src, "(def x{i} {i})").unwrap();
+        writeln!(src, "(def k{i} [:tag :value {{:name :sym}}])").unwrap();
+    }
+    src.push_str("(+");
+    for i in 0..2_000 {
+        write!(src, " x{i} (nth 0 [x{i} k{i}])").unwrap();
+    }
+    src.push(')');
+
+    let runs = 20;
+    let start = Instant::now();
+    let mut total = Value::Nil;
+    for _ in 0..runs {
+        let env = Rc::new(RefCell::new(Env::new()));
+        register_builtins(&mut env.borrow_mut());
+        for form in vaca::parse(&src).expect("bench program parses") {
+            total = eval(&form, &env).expect("bench program evaluates");
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "4k defs + 10k symbol/keyword refs: {:?}/run (sum {total})",
+        elapsed / runs
+    );
+}
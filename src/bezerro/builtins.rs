@@ -1,12 +1,19 @@
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::f64::consts::PI;
-use std::io::{self, BufRead, Write};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
 use std::rc::Rc;
 
-use crate::bezerro::env::Env;
+use crate::bezerro::env::{root_env, Env};
 use crate::bezerro::error::EvalError;
-use crate::bezerro::eval::apply;
-use crate::bezerro::value::{BuiltinFn, Value};
+use crate::bezerro::eval::{apply, node_to_form};
+use crate::bezerro::lazy_seq::LazySeq;
+use crate::bezerro::value::{BuiltinFn, FnvHasher, Value};
+use crate::vedn::{Kind, Node, Number, NumberSuffix, Parser, ParserConfig, Span};
 
 pub fn register_builtins(env: &mut Env) {
     env.define("pi".into(), Value::Float(PI));
@@ -30,6 +37,11 @@ pub fn register_builtins(env: &mut Env) {
     env.define("<=".into(), builtin("<=", builtin_lte));
     env.define("==".into(), builtin("==", builtin_eq));
     env.define("!=".into(), builtin("!=", builtin_neq));
+    env.define(
+        "seq-equal?".into(),
+        builtin("seq-equal?", builtin_seq_equal),
+    );
+    env.define("hash".into(), builtin("hash", builtin_hash));
 
     // logic
     env.define("&".into(), builtin("&", builtin_and));
@@ -37,36 +49,473 @@ pub fn register_builtins(env: &mut Env) {
 
     // io
     env.define("readln".into(), builtin("readln", builtin_readln));
+    env.define(
+        "reduce-lines".into(),
+        builtin("reduce-lines", builtin_reduce_lines),
+    );
     env.define("format".into(), builtin("format", builtin_format));
     env.define("print".into(), builtin("print", builtin_print));
     env.define("println".into(), builtin("println", builtin_println));
 
+    // strings
+    env.define("str".into(), builtin("str", builtin_str));
+    env.define("char+".into(), builtin("char+", builtin_char_add));
+    env.define("pad-right".into(), builtin("pad-right", builtin_pad_right));
+    env.define("table".into(), builtin("table", builtin_table));
+
     // parsing
     env.define("parse-int".into(), builtin("parse-int", builtin_parse_int));
     env.define(
         "parse-float".into(),
         builtin("parse-float", builtin_parse_float),
     );
+    env.define("int".into(), builtin("int", builtin_int));
+    env.define("float".into(), builtin("float", builtin_float));
+    env.define("num".into(), builtin("num", builtin_num));
+    env.define(
+        "read-all-string".into(),
+        builtin("read-all-string", builtin_read_all_string),
+    );
 
     // collections
     env.define("concat".into(), builtin("concat", builtin_concat));
     env.define("append".into(), builtin("append", builtin_append));
     env.define("prepend".into(), builtin("prepend", builtin_prepend));
     env.define("nth".into(), builtin("nth", builtin_nth));
+    env.define("subvec".into(), builtin("subvec", builtin_subvec));
     env.define("map".into(), builtin("map", builtin_map));
     env.define("reduce".into(), builtin("reduce", builtin_reduce));
     env.define("scan".into(), builtin("scan", builtin_scan));
+    env.define("iterate-n".into(), builtin("iterate-n", builtin_iterate_n));
+    env.define("iterate".into(), builtin("iterate", builtin_iterate));
+    env.define("take".into(), builtin("take", builtin_take));
+    env.define("drop".into(), builtin("drop", builtin_drop));
     env.define("filter".into(), builtin("filter", builtin_filter));
+    env.define("remove".into(), builtin("remove", builtin_remove));
+    env.define("sort".into(), builtin("sort", builtin_sort));
+    env.define("sort-by".into(), builtin("sort-by", builtin_sort_by));
+    env.define(
+        "every-pred".into(),
+        builtin("every-pred", builtin_every_pred),
+    );
+    env.define("some-fn".into(), builtin("some-fn", builtin_some_fn));
     env.define("find".into(), builtin("find", builtin_find));
+    env.define(
+        "find-index".into(),
+        builtin("find-index", builtin_find_index),
+    );
+    env.define("into".into(), builtin("into", builtin_into));
+    env.define("apply".into(), builtin("apply", builtin_apply));
+    env.define("get".into(), builtin("get", builtin_get));
+    env.define("has-key?".into(), builtin("has-key?", builtin_has_key));
+    env.define("has-val?".into(), builtin("has-val?", builtin_has_val));
+    env.define("transient".into(), builtin("transient", builtin_transient));
+    env.define("conj!".into(), builtin("conj!", builtin_conj_mut));
+    env.define("assoc!".into(), builtin("assoc!", builtin_assoc_mut));
+    env.define(
+        "persistent!".into(),
+        builtin("persistent!", builtin_persistent),
+    );
 
     // \"macro\" fns that we treat as builtins for now
     env.define("assert".into(), builtin("assert", builtin_assert));
 }
 
+/// Signature and one-line description of a builtin, shown by `help`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinDoc {
+    pub name: &'static str,
+    /// Parameter names as written in a call, e.g. `"f init array"`.
+    pub params: &'static str,
+    pub doc: &'static str,
+}
+
+const BUILTIN_DOCS: &[BuiltinDoc] = &[
+    BuiltinDoc {
+        name: "pi",
+        params: "",
+        doc: "3.1415926 pi constant",
+    },
+    BuiltinDoc {
+        name: "+",
+        params: "a b",
+        doc: "sums `a` and `b`",
+    },
+    BuiltinDoc {
+        name: "-",
+        params: "a b",
+        doc: "subtracts `b` from `a`",
+    },
+    BuiltinDoc {
+        name: "*",
+        params: "a b",
+        doc: "multiplies `a` by `b`",
+    },
+    BuiltinDoc {
+        name: "/",
+        params: "a b",
+        doc: "divides `a` by `b`",
+    },
+    BuiltinDoc {
+        name: "//",
+        params: "a b",
+        doc: "integer division of `a` and `b`",
+    },
+    BuiltinDoc {
+        name: "^",
+        params: "a b",
+        doc: "`a` to the power of `b`",
+    },
+    BuiltinDoc {
+        name: "mod",
+        params: "a b",
+        doc: "integer division remainder of `a` divided by `b`",
+    },
+    BuiltinDoc {
+        name: "brt",
+        params: "a b",
+        doc: "`b`-th root of `a`",
+    },
+    BuiltinDoc {
+        name: "max",
+        params: "a b",
+        doc: "maximum of `a` and `b`",
+    },
+    BuiltinDoc {
+        name: "min",
+        params: "a b",
+        doc: "minimum of `a` and `b`",
+    },
+    BuiltinDoc {
+        name: ">",
+        params: "a b",
+        doc: "`a` greater than `b`",
+    },
+    BuiltinDoc {
+        name: "<",
+        params: "a b",
+        doc: "`a` less than `b`",
+    },
+    BuiltinDoc {
+        name: ">=",
+        params: "a b",
+        doc: "`a` greater or equals to `b`",
+    },
+    BuiltinDoc {
+        name: "<=",
+        params: "a b",
+        doc: "`a` less or equals to `b`",
+    },
+    BuiltinDoc {
+        name: "==",
+        params: "a b",
+        doc: "`a` equals to `b`",
+    },
+    BuiltinDoc {
+        name: "!=",
+        params: "a b",
+        doc: "`a` not equals to `b`",
+    },
+    BuiltinDoc {
+        name: "seq-equal?",
+        params: "a b",
+        doc: "like `==`, but a list and a vector with equal elements are equal",
+    },
+    BuiltinDoc {
+        name: "hash",
+        params: "v",
+        doc: "deterministic int hash of `v`; equal values hash equally",
+    },
+    BuiltinDoc {
+        name: "&",
+        params: "a b",
+        doc: "logic and of `a` and `b`",
+    },
+    BuiltinDoc {
+        name: "|",
+        params: "a b",
+        doc: "logic or of `a` and `b`",
+    },
+    BuiltinDoc {
+        name: "readln",
+        params: "",
+        doc: "reads a line from the terminal",
+    },
+    BuiltinDoc {
+        name: "reduce-lines",
+        params: "path f init",
+        doc:
+            "folds `(f acc line)` over the lines of the file at `path`, reading one line at a time",
+    },
+    BuiltinDoc {
+        name: "format",
+        params: "elems...",
+        doc: "turns the values into a string and concatenates them",
+    },
+    BuiltinDoc {
+        name: "print",
+        params: "elems...",
+        doc: "like `format`, but prints the string instead of returning it",
+    },
+    BuiltinDoc {
+        name: "println",
+        params: "elems...",
+        doc: "like `print`, but prints a linefeed at the end",
+    },
+    BuiltinDoc {
+        name: "str",
+        params: "elems...",
+        doc: "concatenates the values into a string; strings and chars are inserted as-is",
+    },
+    BuiltinDoc {
+        name: "char+",
+        params: "c n",
+        doc: "the char whose code point is `n` past `c`",
+    },
+    BuiltinDoc {
+        name: "pad-right",
+        params: "s width",
+        doc: "`s` padded with spaces on the right to `width` chars",
+    },
+    BuiltinDoc {
+        name: "table",
+        params: "rows :header? header",
+        doc: "lays out a vector of rows as a string with aligned columns",
+    },
+    BuiltinDoc {
+        name: "parse-int",
+        params: "text",
+        doc: "turns the string `text` into an int",
+    },
+    BuiltinDoc {
+        name: "parse-float",
+        params: "text",
+        doc: "turns the string `text` into a float",
+    },
+    BuiltinDoc {
+        name: "int",
+        params: "x",
+        doc: "the number `x` as an int, truncating floats toward zero",
+    },
+    BuiltinDoc {
+        name: "float",
+        params: "x",
+        doc: "the number `x` as a float",
+    },
+    BuiltinDoc {
+        name: "num",
+        params: "text",
+        doc: "reads the string `text` as a number literal: an int or a float",
+    },
+    BuiltinDoc {
+        name: "read-all-string",
+        params: "text",
+        doc: "parses every form in `text` into a vector of unevaluated data",
+    },
+    BuiltinDoc {
+        name: "concat",
+        params: "init end",
+        doc: "concatenates two vectors, putting `end` after `init`",
+    },
+    BuiltinDoc {
+        name: "append",
+        params: "elem array",
+        doc: "returns `array` with `elem` put at the start",
+    },
+    BuiltinDoc {
+        name: "prepend",
+        params: "elem array",
+        doc: "returns `array` with `elem` put at the end",
+    },
+    BuiltinDoc {
+        name: "nth",
+        params: "n array",
+        doc: "the `n`-th element of `array`, counting from 0",
+    },
+    BuiltinDoc {
+        name: "subvec",
+        params: "v start end?",
+        doc: "a copy of the elements of vector `v` from `start` up to, not including, `end`",
+    },
+    BuiltinDoc {
+        name: "map",
+        params: "f array",
+        doc: "applies `f` to each element of `array`; lazily if `array` is a lazy seq",
+    },
+    BuiltinDoc {
+        name: "reduce",
+        params: "f init array",
+        doc: "folds `array` with `f`, starting from `init`",
+    },
+    BuiltinDoc {
+        name: "scan",
+        params: "f init array",
+        doc: "like `reduce`, but returns every intermediate result",
+    },
+    BuiltinDoc {
+        name: "iterate-n",
+        params: "f x n",
+        doc: "the vector of the first `n` values of `x`, `(f x)`, `(f (f x))`, ...",
+    },
+    BuiltinDoc {
+        name: "iterate",
+        params: "f x",
+        doc: "the infinite lazy seq of `x`, `(f x)`, `(f (f x))`, ...",
+    },
+    BuiltinDoc {
+        name: "take",
+        params: "n coll",
+        doc: "the first `n` elements of `coll`; a vector when `coll` is a lazy seq",
+    },
+    BuiltinDoc {
+        name: "drop",
+        params: "n coll",
+        doc: "`coll` without its first `n` elements; lazy if `coll` is",
+    },
+    BuiltinDoc {
+        name: "filter",
+        params: "f array",
+        doc: "the elements of `array` for which `f` is truthy; lazily if `array` is a lazy seq",
+    },
+    BuiltinDoc {
+        name: "remove",
+        params: "f array",
+        doc: "the elements of `array` for which `f` is falsy; lazily if `array` is a lazy seq",
+    },
+    BuiltinDoc {
+        name: "sort",
+        params: "cmp? coll",
+        doc: "a stably sorted vector of `coll`, by the natural order or the comparator `cmp`",
+    },
+    BuiltinDoc {
+        name: "sort-by",
+        params: "keyfn cmp? coll",
+        doc: "a stably sorted vector of `coll`, ordered by `(keyfn x)`",
+    },
+    BuiltinDoc {
+        name: "every-pred",
+        params: "preds...",
+        doc: "a function that is true when every one of `preds` is truthy for all its arguments",
+    },
+    BuiltinDoc {
+        name: "some-fn",
+        params: "preds...",
+        doc: "a function returning the first truthy result of `preds` on its arguments, or nil",
+    },
+    BuiltinDoc {
+        name: "find",
+        params: "f array | m k",
+        doc:
+            "the first element of `array` for which `f` is truthy, or the `[k v]` entry of map `m`",
+    },
+    BuiltinDoc {
+        name: "find-index",
+        params: "f array",
+        doc: "the index of the first element of `array` for which `f` is truthy, or nil",
+    },
+    BuiltinDoc {
+        name: "into",
+        params: "target coll",
+        doc: "pours the elements of `coll` into `target` (vector, list, set, map or string)",
+    },
+    BuiltinDoc {
+        name: "apply",
+        params: "f coll",
+        doc: "calls `f` with the elements of `coll` as arguments",
+    },
+    BuiltinDoc {
+        name: "get",
+        params: "m k default?",
+        doc: "the value of key `k` in map `m`, or `default` (nil if omitted) when absent",
+    },
+    BuiltinDoc {
+        name: "has-key?",
+        params: "m k",
+        doc: "whether the map `m` has the key `k`",
+    },
+    BuiltinDoc {
+        name: "has-val?",
+        params: "m v",
+        doc: "whether some key of the map `m` is bound to `v`",
+    },
+    BuiltinDoc {
+        name: "transient",
+        params: "coll",
+        doc: "a mutable builder holding a copy of the vector or map `coll`",
+    },
+    BuiltinDoc {
+        name: "conj!",
+        params: "t x",
+        doc: "appends `x` to the transient vector `t` in place and returns `t`",
+    },
+    BuiltinDoc {
+        name: "assoc!",
+        params: "t k v",
+        doc: "binds `k` to `v` in the transient map `t` in place and returns `t`",
+    },
+    BuiltinDoc {
+        name: "persistent!",
+        params: "t",
+        doc: "the collection built in the transient `t`; `t` can't be used afterwards",
+    },
+    BuiltinDoc {
+        name: "assert",
+        params: "values...",
+        doc: "fails if some value is falsy",
+    },
+];
+
+/// Looks up the documentation of the builtin registered as `name`.
+pub fn builtin_doc(name: &str) -> Option<&'static BuiltinDoc> {
+    BUILTIN_DOCS.iter().find(|d| d.name == name)
+}
+
+/// Describes `name` as bound in `env`: a call signature followed by its docstring, if any.
+///
+/// User docstrings come from `(defn name "doc" [params] body...)`; builtins are described by
+/// [`builtin_doc`]. Returns `None` if `name` is unbound.
+pub fn help_text(name: &str, env: &Rc<RefCell<Env>>) -> Option<String> {
+    let value = env.borrow().get(name)?;
+    let builtin = match &value {
+        Value::Builtin { name, .. } => builtin_doc(name),
+        // Builtin constants such as `pi`.
+        Value::Lambda { .. } | Value::Macro { .. } | Value::Native { .. } => None,
+        _ => builtin_doc(name),
+    };
+    let params = match &value {
+        Value::Lambda { params, .. } | Value::Macro { params, .. } => Some(params.join(" ")),
+        Value::Builtin { .. } => builtin.map(|d| d.params.to_string()),
+        _ => None,
+    };
+    let mut out = match params {
+        Some(p) if p.is_empty() => format!("({name})"),
+        Some(p) => format!("({name} {p})"),
+        None => name.to_string(),
+    };
+    let user_doc = env.borrow().docs().borrow().get(name).cloned();
+    if let Some(doc) = user_doc.as_deref().or(builtin.map(|d| d.doc)) {
+        out.push_str("\n  ");
+        out.push_str(doc);
+    }
+    Some(out)
+}
+
 fn builtin(name: &'static str, func: BuiltinFn) -> Value {
     Value::Builtin { name, func }
 }
 
+/// A non-negative int argument, such as the `n` of `take`.
+fn expect_count(form: &str, n: &Value) -> Result<usize, EvalError> {
+    let Value::Int(n) = *n else {
+        return Err(EvalError::TypeError {
+            expected: "int",
+            got: n.type_name(),
+        });
+    };
+    usize::try_from(n)
+        .map_err(|_| EvalError::Custom(format!("{form}: count must not be negative (got {n})")))
+}
+
 fn expect_arity(args: &[Value], n: usize) -> Result<(), EvalError> {
     if args.len() != n {
         return Err(EvalError::ArityError {
@@ -338,6 +787,19 @@ fn builtin_neq(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalErr
     Ok(Value::Bool(args[0] != args[1]))
 }
 
+fn builtin_seq_equal(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    Ok(Value::Bool(args[0].seq_eq(&args[1])))
+}
+
+fn builtin_hash(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 1)?;
+    // FNV-1a is fully specified, so hashes are stable across runs, builds and platforms.
+    let mut hasher = FnvHasher::default();
+    args[0].hash(&mut hasher);
+    Ok(Value::Int(hasher.finish() as i64))
+}
+
 fn builtin_and(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
     expect_arity(args, 2)?;
     let Value::Bool(a) = args[0] else {
@@ -388,40 +850,178 @@ fn builtin_readln(_args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, Eva
     Ok(Value::String(input))
 }
 
-fn builtin_format(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
-    if args.len() == 1 {
-        if let Value::Vector(v) = &args[0] {
-            let mut out = String::new();
-            for item in v {
-                out.push_str(&string_for_io(item));
-            }
-            return Ok(Value::String(out));
-        }
+/// Folds `(f acc line)` over a file one line at a time, so memory use doesn't grow with its size.
+fn builtin_reduce_lines(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 3)?;
+    let Value::String(path) = &args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "string",
+            got: args[0].type_name(),
+        });
+    };
+    // Relative paths resolve like `use` does: against the running file's directory.
+    let path = match root_env(env).borrow().source_dir() {
+        Some(dir) => dir.join(path),
+        None => PathBuf::from(path),
+    };
+    let io_error =
+        |e: io::Error| EvalError::Custom(format!("reduce-lines: {}: {e}", path.display()));
+
+    let file = File::open(&path).map_err(io_error)?;
+    let mut acc = args[2].clone();
+    for line in BufReader::new(file).lines() {
+        acc = apply(
+            &args[1],
+            &[acc, Value::String(line.map_err(io_error)?)],
+            env,
+        )?;
     }
-    let mut out = String::new();
-    for a in args {
-        out.push_str(&string_for_io(a));
+    Ok(acc)
+}
+
+fn builtin_format(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let items = match args {
+        [Value::Vector(v)] => v.as_slice(),
+        _ => args,
+    };
+    Ok(Value::String(format_for_io(items)))
+}
+
+fn builtin_print(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    env.borrow().write_output(&format_for_io(args))?;
+    Ok(Value::Nil)
+}
+
+fn builtin_println(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let mut s = format_for_io(args);
+    s.push('\n');
+    env.borrow().write_output(&s)?;
+    Ok(Value::Nil)
+}
+
+fn builtin_str(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let mut out = String::with_capacity(args.iter().map(io_len_hint).sum());
+    for item in args {
+        push_str_value(&mut out, item);
     }
     Ok(Value::String(out))
 }
 
-fn builtin_print(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
-    let Value::String(s) = builtin_format(args, _env)? else {
-        unreachable!("format returns string");
+fn builtin_char_add(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let Value::Char(c) = args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "char",
+            got: args[0].type_name(),
+        });
     };
-    print!("{}", s);
-    io::stdout()
-        .flush()
-        .map_err(|e| EvalError::Custom(format!("stdout flush failed: {e}")))?;
-    Ok(Value::Nil)
+    let Value::Int(n) = args[1] else {
+        return Err(EvalError::TypeError {
+            expected: "int",
+            got: args[1].type_name(),
+        });
+    };
+    (c as i64)
+        .checked_add(n)
+        .and_then(|code| u32::try_from(code).ok())
+        .and_then(char::from_u32)
+        .map(Value::Char)
+        .ok_or_else(|| EvalError::Custom(format!("char+: \\{c} + {n} is not a valid char")))
 }
 
-fn builtin_println(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
-    let Value::String(s) = builtin_format(args, _env)? else {
-        unreachable!("format returns string");
+fn builtin_pad_right(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let Value::Int(width) = args[1] else {
+        return Err(EvalError::TypeError {
+            expected: "int",
+            got: args[1].type_name(),
+        });
     };
-    println!("{}", s);
-    Ok(Value::Nil)
+    let Ok(width) = usize::try_from(width) else {
+        return Err(EvalError::Custom(format!(
+            "pad-right: width {width} is negative"
+        )));
+    };
+    let mut out = String::new();
+    push_str_value(&mut out, &args[0]);
+    pad_right(&mut out, width);
+    Ok(Value::String(out))
+}
+
+/// Pads `s` with spaces up to `width` chars.
+fn pad_right(s: &mut String, width: usize) {
+    let len = s.chars().count();
+    s.extend(std::iter::repeat_n(' ', width.saturating_sub(len)));
+}
+
+fn builtin_table(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let (rows, header) = match args {
+        [rows] => (rows, None),
+        [rows, Value::Keyword(k), header] if k.is_bare("header") => (rows, Some(header)),
+        _ => {
+            return Err(EvalError::Custom(
+                "table expects: (table rows) or (table rows :header [names...])".to_string(),
+            ))
+        }
+    };
+    let as_cells = |row: &Value| -> Result<Vec<String>, EvalError> {
+        let Value::Vector(cells) = row else {
+            return Err(EvalError::TypeError {
+                expected: "vector",
+                got: row.type_name(),
+            });
+        };
+        Ok(cells
+            .iter()
+            .map(|cell| {
+                let mut s = String::new();
+                push_str_value(&mut s, cell);
+                s
+            })
+            .collect())
+    };
+    let Value::Vector(rows) = rows else {
+        return Err(EvalError::TypeError {
+            expected: "vector",
+            got: rows.type_name(),
+        });
+    };
+    let header = header.map(as_cells).transpose()?;
+    let body = rows.iter().map(as_cells).collect::<Result<Vec<_>, _>>()?;
+
+    // Ragged rows are fine: missing cells are treated as empty.
+    let all_rows = || header.iter().chain(&body);
+    let columns = all_rows().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; columns];
+    for row in all_rows() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let render_row = |row: &[String]| {
+        let mut line = String::new();
+        let mut column_end = 0;
+        for (i, width) in widths.iter().enumerate() {
+            if i != 0 {
+                line.push_str("  ");
+                column_end += 2;
+            }
+            line.push_str(row.get(i).map_or("", String::as_str));
+            column_end += width;
+            pad_right(&mut line, column_end);
+        }
+        line.truncate(line.trim_end().len());
+        line
+    };
+    let mut lines = Vec::with_capacity(body.len() + 2);
+    if let Some(header) = &header {
+        lines.push(render_row(header));
+        let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        lines.push(render_row(&rule));
+    }
+    lines.extend(body.iter().map(|row| render_row(row)));
+    Ok(Value::String(lines.join("\n")))
 }
 
 fn builtin_parse_int(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
@@ -452,6 +1052,92 @@ fn builtin_parse_float(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value,
     Ok(Value::Float(n))
 }
 
+fn builtin_int(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 1)?;
+    match args[0] {
+        Value::Int(n) => Ok(Value::Int(n)),
+        // `i64::MIN` is exactly representable but `i64::MAX` isn't, hence the asymmetric bounds.
+        Value::Float(f) if f.is_finite() && f >= i64::MIN as f64 && f < i64::MAX as f64 => {
+            Ok(Value::Int(f.trunc() as i64))
+        }
+        Value::Float(f) => Err(EvalError::Custom(format!(
+            "int: {} has no int value",
+            Value::Float(f)
+        ))),
+        ref other => Err(EvalError::TypeError {
+            expected: "number",
+            got: other.type_name(),
+        }),
+    }
+}
+
+fn builtin_float(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 1)?;
+    match args[0] {
+        Value::Int(n) => Ok(Value::Float(n as f64)),
+        Value::Float(f) => Ok(Value::Float(f)),
+        ref other => Err(EvalError::TypeError {
+            expected: "number",
+            got: other.type_name(),
+        }),
+    }
+}
+
+/// Reads a number with the same syntax as a literal, so `"4"` gives an int and `"4.5"` a float.
+///
+/// Only surrounding whitespace may accompany the number: comments, discards and annotations are
+/// rejected, and so are numbers an int or float can't hold, instead of reading them as `0`.
+fn builtin_num(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 1)?;
+    let Value::String(s) = &args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "string",
+            got: args[0].type_name(),
+        });
+    };
+    let text = s.trim();
+    let nodes = Parser::with_config(text, ParserConfig::untrusted()).parse_all();
+    let number = match nodes.as_deref() {
+        Ok(
+            [Node {
+                kind: Kind::Number(number),
+                annotation: None,
+                span,
+                ..
+            }],
+        ) if *span == Span::new(0, text.len()) => number,
+        _ => return Err(EvalError::Custom(format!("num: not a number: {s:?}"))),
+    };
+    if number.suffix() != NumberSuffix::None {
+        return Err(EvalError::Custom(format!(
+            "num: precision suffixes are not supported: {s:?}"
+        )));
+    }
+    let value = match number {
+        Number::Int { lexeme, .. } => lexeme.parse().ok().map(Value::Int),
+        Number::Float { lexeme, .. } => lexeme
+            .parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .map(Value::Float),
+    };
+    value.ok_or_else(|| EvalError::Custom(format!("num: out of range: {s:?}")))
+}
+
+fn builtin_read_all_string(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 1)?;
+    let Value::String(s) = &args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "string",
+            got: args[0].type_name(),
+        });
+    };
+    let nodes = Parser::with_config(s, ParserConfig::untrusted())
+        .parse_all()
+        .map_err(|error| EvalError::ParseError { path: None, error })?;
+    Ok(Value::Vector(nodes.iter().map(node_to_form).collect()))
+}
+
 fn builtin_concat(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
     expect_arity(args, 2)?;
     let Value::Vector(a) = &args[0] else {
@@ -529,9 +1215,67 @@ fn builtin_nth(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalErr
     })
 }
 
+fn builtin_subvec(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let (v, start, end) = match args {
+        [v, start] => (v, start, None),
+        [v, start, end] => (v, start, Some(end)),
+        _ => {
+            return Err(EvalError::Custom(
+                "subvec expects: (subvec v start) or (subvec v start end)".to_string(),
+            ))
+        }
+    };
+    let Value::Vector(v) = v else {
+        return Err(EvalError::TypeError {
+            expected: "vector",
+            got: v.type_name(),
+        });
+    };
+    let start = slice_bound("subvec", start, v.len())?;
+    let end = match end {
+        Some(end) => slice_bound("subvec", end, v.len())?,
+        None => v.len(),
+    };
+    if start > end {
+        return Err(EvalError::Custom(format!(
+            "subvec: start {start} is past end {end}"
+        )));
+    }
+    Ok(Value::Vector(v[start..end].to_vec()))
+}
+
+/// A slice boundary into a sequence of length `len`; unlike an element index it may equal `len`.
+fn slice_bound(form: &str, n: &Value, len: usize) -> Result<usize, EvalError> {
+    let Value::Int(n) = *n else {
+        return Err(EvalError::TypeError {
+            expected: "int",
+            got: n.type_name(),
+        });
+    };
+    let Ok(index) = usize::try_from(n) else {
+        return Err(EvalError::Custom(format!("{form}: index {n} is negative")));
+    };
+    if index > len {
+        return Err(EvalError::IndexOutOfBounds { index, len });
+    }
+    Ok(index)
+}
+
 fn builtin_map(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
     expect_arity(args, 2)?;
     let f = args[0].clone();
+    if let Value::LazySeq(seq) = &args[1] {
+        let (source, mut index) = (seq.clone(), 0);
+        // `index` only advances once `f` succeeds, so a failed element is retried, not skipped.
+        return Ok(lazy(move |env| {
+            let Some(item) = source.get(index, env)? else {
+                return Ok(None);
+            };
+            let mapped = apply(&f, &[item], env)?;
+            index += 1;
+            Ok(Some(mapped))
+        }));
+    }
     let Value::Vector(v) = &args[1] else {
         return Err(EvalError::TypeError {
             expected: "vector",
@@ -540,7 +1284,7 @@ fn builtin_map(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalErro
     };
     let mut out = Vec::with_capacity(v.len());
     for item in v {
-        out.push(apply(&f, &[item.clone()], env)?);
+        out.push(apply(&f, std::slice::from_ref(item), env)?);
     }
     Ok(Value::Vector(out))
 }
@@ -579,9 +1323,111 @@ fn builtin_scan(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalErr
     Ok(Value::Vector(out))
 }
 
+fn builtin_iterate_n(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 3)?;
+    let n = expect_count("iterate-n", &args[2])?;
+    let mut out = Vec::with_capacity(n);
+    if n > 0 {
+        out.push(args[1].clone());
+    }
+    // `f` runs n - 1 times: the last element is never passed to it.
+    while let Some(last) = out.last().filter(|_| out.len() < n) {
+        let next = apply(&args[0], std::slice::from_ref(last), env)?;
+        out.push(next);
+    }
+    Ok(Value::Vector(out))
+}
+
+fn builtin_iterate(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let (f, init) = (args[0].clone(), args[1].clone());
+    let mut last: Option<Value> = None;
+    Ok(lazy(move |env| {
+        let next = match &last {
+            None => init.clone(),
+            Some(x) => apply(&f, std::slice::from_ref(x), env)?,
+        };
+        last = Some(next.clone());
+        Ok(Some(next))
+    }))
+}
+
+fn builtin_take(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let n = expect_count("take", &args[0])?;
+    match &args[1] {
+        Value::Vector(v) => Ok(Value::Vector(v.iter().take(n).cloned().collect())),
+        Value::List(v) => Ok(Value::List(v.iter().take(n).cloned().collect())),
+        Value::LazySeq(seq) => {
+            let mut out = Vec::with_capacity(n);
+            for i in 0..n {
+                match seq.get(i, env)? {
+                    Some(item) => out.push(item),
+                    None => break,
+                }
+            }
+            Ok(Value::Vector(out))
+        }
+        other => Err(EvalError::TypeError {
+            expected: "vector, list or lazy-seq",
+            got: other.type_name(),
+        }),
+    }
+}
+
+fn builtin_drop(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let n = expect_count("drop", &args[0])?;
+    match &args[1] {
+        Value::Vector(v) => Ok(Value::Vector(v.iter().skip(n).cloned().collect())),
+        Value::List(v) => Ok(Value::List(v.iter().skip(n).cloned().collect())),
+        Value::LazySeq(seq) => {
+            let (source, mut index) = (seq.clone(), n);
+            Ok(lazy(move |env| {
+                let item = source.get(index, env)?;
+                index += 1;
+                Ok(item)
+            }))
+        }
+        other => Err(EvalError::TypeError {
+            expected: "vector, list or lazy-seq",
+            got: other.type_name(),
+        }),
+    }
+}
+
+fn lazy(
+    step: impl FnMut(&Rc<RefCell<Env>>) -> Result<Option<Value>, EvalError> + 'static,
+) -> Value {
+    Value::LazySeq(Rc::new(LazySeq::new(step)))
+}
+
 fn builtin_filter(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    filter_impl(args, env, true)
+}
+
+fn builtin_remove(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    filter_impl(args, env, false)
+}
+
+/// `filter` when `keep` is true, `remove` otherwise: keeps the elements whose truthiness under `f`
+/// equals `keep`.
+fn filter_impl(args: &[Value], env: &Rc<RefCell<Env>>, keep: bool) -> Result<Value, EvalError> {
     expect_arity(args, 2)?;
     let f = args[0].clone();
+    if let Value::LazySeq(seq) = &args[1] {
+        let (source, mut index) = (seq.clone(), 0);
+        return Ok(lazy(move |env| loop {
+            let Some(item) = source.get(index, env)? else {
+                return Ok(None);
+            };
+            let matched = apply(&f, std::slice::from_ref(&item), env)?.is_truthy() == keep;
+            index += 1;
+            if matched {
+                return Ok(Some(item));
+            }
+        }));
+    }
     let Value::Vector(v) = &args[1] else {
         return Err(EvalError::TypeError {
             expected: "vector",
@@ -590,15 +1436,177 @@ fn builtin_filter(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalE
     };
     let mut out = Vec::with_capacity(v.len());
     for item in v {
-        if apply(&f, &[item.clone()], env)?.is_truthy() {
+        if apply(&f, std::slice::from_ref(item), env)?.is_truthy() == keep {
             out.push(item.clone());
         }
     }
     Ok(Value::Vector(out))
 }
 
+fn builtin_sort(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let (cmp, coll) = match args {
+        [coll] => (None, coll),
+        [cmp, coll] => (Some(cmp), coll),
+        _ => {
+            return Err(EvalError::Custom(
+                "sort expects: (sort coll) or (sort cmp coll)".to_string(),
+            ))
+        }
+    };
+    let items = sort_items(coll)?;
+    let sorted = merge_sort(items, &mut |a, b| sorts_before(cmp, a, b, env))?;
+    Ok(Value::Vector(sorted))
+}
+
+fn builtin_sort_by(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let (keyfn, cmp, coll) = match args {
+        [keyfn, coll] => (keyfn, None, coll),
+        [keyfn, cmp, coll] => (keyfn, Some(cmp), coll),
+        _ => {
+            return Err(EvalError::Custom(
+                "sort-by expects: (sort-by keyfn coll) or (sort-by keyfn cmp coll)".to_string(),
+            ))
+        }
+    };
+    // Compute each key once up front instead of on every comparison.
+    let keyed = sort_items(coll)?
+        .into_iter()
+        .map(|item| Ok((apply(keyfn, std::slice::from_ref(&item), env)?, item)))
+        .collect::<Result<Vec<_>, EvalError>>()?;
+    let sorted = merge_sort(keyed, &mut |(a, _), (b, _)| sorts_before(cmp, a, b, env))?;
+    Ok(Value::Vector(
+        sorted.into_iter().map(|(_, item)| item).collect(),
+    ))
+}
+
+fn sort_items(coll: &Value) -> Result<Vec<Value>, EvalError> {
+    match coll {
+        Value::Vector(v) | Value::List(v) => Ok(v.clone()),
+        other => Err(EvalError::TypeError {
+            expected: "vector or list",
+            got: other.type_name(),
+        }),
+    }
+}
+
+/// Whether `a` goes strictly before `b`. A comparator `cmp` may return an int (negative means
+/// `a` first, as in `compare`) or a bool (a less-than predicate); without one, the natural
+/// order applies.
+fn sorts_before(
+    cmp: Option<&Value>,
+    a: &Value,
+    b: &Value,
+    env: &Rc<RefCell<Env>>,
+) -> Result<bool, EvalError> {
+    let Some(cmp) = cmp else {
+        return Ok(natural_cmp(a, b)? == Ordering::Less);
+    };
+    match apply(cmp, &[a.clone(), b.clone()], env)? {
+        Value::Int(n) => Ok(n < 0),
+        Value::Float(n) => Ok(n < 0.0),
+        Value::Bool(less) => Ok(less),
+        other => Err(EvalError::TypeError {
+            expected: "int or bool from comparator",
+            got: other.type_name(),
+        }),
+    }
+}
+
+/// The order `sort` uses without a comparator: numbers by value; strings, chars, keywords and
+/// symbols by text; vectors element by element.
+fn natural_cmp(a: &Value, b: &Value) -> Result<Ordering, EvalError> {
+    match (a, b) {
+        (Value::Int(x), Value::Int(y)) => Ok(x.cmp(y)),
+        (Value::String(x), Value::String(y)) => Ok(x.cmp(y)),
+        (Value::Char(x), Value::Char(y)) => Ok(x.cmp(y)),
+        (Value::Symbol(x), Value::Symbol(y)) => Ok(x.cmp(y)),
+        (Value::Keyword(x), Value::Keyword(y)) => {
+            Ok((x.namespace.as_deref(), &*x.name).cmp(&(y.namespace.as_deref(), &*y.name)))
+        }
+        (Value::Vector(x), Value::Vector(y)) => {
+            for (p, q) in x.iter().zip(y) {
+                let ord = natural_cmp(p, q)?;
+                if ord != Ordering::Equal {
+                    return Ok(ord);
+                }
+            }
+            Ok(x.len().cmp(&y.len()))
+        }
+        _ => {
+            let (x, y, _) = promote(a, b)?;
+            Ok(x.partial_cmp(&y).unwrap_or(Ordering::Equal))
+        }
+    }
+}
+
+/// Stable merge sort with a fallible `before` test. Unlike `slice::sort_by`, a user comparator
+/// that isn't a consistent order can't panic; it only makes the result order unspecified.
+fn merge_sort<T>(
+    mut items: Vec<T>,
+    before: &mut dyn FnMut(&T, &T) -> Result<bool, EvalError>,
+) -> Result<Vec<T>, EvalError> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+    let right = items.split_off(items.len() / 2);
+    let (left, right) = (merge_sort(items, before)?, merge_sort(right, before)?);
+
+    let mut out = Vec::with_capacity(left.len() + right.len());
+    let (mut left, mut right) = (left.into_iter().peekable(), right.into_iter().peekable());
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // Taking from the left unless the right is strictly first keeps equal elements in order.
+        let next = if before(b, a)? { &mut right } else { &mut left };
+        out.extend(next.next());
+    }
+    out.extend(left);
+    out.extend(right);
+    Ok(out)
+}
+
+fn builtin_every_pred(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let preds = args.to_vec();
+    Ok(Value::Native {
+        name: "every-pred".to_string(),
+        func: Rc::new(move |args, env| {
+            for pred in &preds {
+                for arg in args {
+                    if !apply(pred, std::slice::from_ref(arg), env)?.is_truthy() {
+                        return Ok(Value::Bool(false));
+                    }
+                }
+            }
+            Ok(Value::Bool(true))
+        }),
+    })
+}
+
+fn builtin_some_fn(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let preds = args.to_vec();
+    Ok(Value::Native {
+        name: "some-fn".to_string(),
+        func: Rc::new(move |args, env| {
+            for pred in &preds {
+                for arg in args {
+                    let result = apply(pred, std::slice::from_ref(arg), env)?;
+                    if result.is_truthy() {
+                        return Ok(result);
+                    }
+                }
+            }
+            Ok(Value::Nil)
+        }),
+    })
+}
+
 fn builtin_find(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
     expect_arity(args, 2)?;
+    // `(find m k)`: the `[k v]` entry, so a key bound to nil isn't mistaken for a missing one.
+    if let Value::Map(m) = &args[0] {
+        return Ok(m
+            .get(&args[1])
+            .map(|v| Value::Vector(vec![args[1].clone(), v.clone()]))
+            .unwrap_or(Value::Nil));
+    }
     let f = args[0].clone();
     let Value::Vector(v) = &args[1] else {
         return Err(EvalError::TypeError {
@@ -607,13 +1615,208 @@ fn builtin_find(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalErr
         });
     };
     for item in v {
-        if apply(&f, &[item.clone()], env)?.is_truthy() {
+        if apply(&f, std::slice::from_ref(item), env)?.is_truthy() {
             return Ok(item.clone());
         }
     }
     Ok(Value::Nil)
 }
 
+fn builtin_find_index(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let Value::Vector(v) = &args[1] else {
+        return Err(EvalError::TypeError {
+            expected: "vector",
+            got: args[1].type_name(),
+        });
+    };
+    for (i, item) in v.iter().enumerate() {
+        if apply(&args[0], std::slice::from_ref(item), env)?.is_truthy() {
+            return Ok(Value::Int(i as i64));
+        }
+    }
+    Ok(Value::Nil)
+}
+
+fn builtin_into(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let items = match &args[1] {
+        Value::Vector(v) | Value::List(v) => v.clone(),
+        Value::Set(s) => s.iter().cloned().collect(),
+        Value::Nil => Vec::new(),
+        other => {
+            return Err(EvalError::TypeError {
+                expected: "vector, list or set",
+                got: other.type_name(),
+            })
+        }
+    };
+    match &args[0] {
+        Value::String(s) => {
+            let mut out = s.clone();
+            out.reserve(items.iter().map(io_len_hint).sum());
+            for item in &items {
+                push_str_value(&mut out, item);
+            }
+            Ok(Value::String(out))
+        }
+        Value::Vector(v) => Ok(Value::Vector(v.iter().cloned().chain(items).collect())),
+        Value::List(v) => Ok(Value::List(v.iter().cloned().chain(items).collect())),
+        Value::Set(s) => {
+            #[allow(clippy::mutable_key_type)]
+            let mut out = s.as_ref().clone();
+            out.extend(items);
+            Ok(Value::Set(Rc::new(out)))
+        }
+        Value::Map(m) => {
+            let mut out = m.as_ref().clone();
+            for item in items {
+                let Value::Vector(pair) = &item else {
+                    return Err(EvalError::TypeError {
+                        expected: "[key value] vector",
+                        got: item.type_name(),
+                    });
+                };
+                let [k, v] = pair.as_slice() else {
+                    return Err(EvalError::Custom(
+                        "into: map entries must be [key value] vectors".to_string(),
+                    ));
+                };
+                out.insert(k.clone(), v.clone());
+            }
+            Ok(Value::Map(Rc::new(out)))
+        }
+        other => Err(EvalError::TypeError {
+            expected: "string, vector, list, set or map",
+            got: other.type_name(),
+        }),
+    }
+}
+
+fn builtin_apply(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let (Value::Vector(items) | Value::List(items)) = &args[1] else {
+        return Err(EvalError::TypeError {
+            expected: "vector or list",
+            got: args[1].type_name(),
+        });
+    };
+    apply(&args[0], items, env)
+}
+
+fn builtin_get(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let (coll, key, default) = match args {
+        [coll, key] => (coll, key, Value::Nil),
+        [coll, key, default] => (coll, key, default.clone()),
+        _ => {
+            return Err(EvalError::Custom(
+                "get expects: (get m k) or (get m k default)".to_string(),
+            ))
+        }
+    };
+    Ok(coll.lookup(key)?.unwrap_or(default))
+}
+
+fn builtin_has_key(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let Value::Map(m) = &args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "map",
+            got: args[0].type_name(),
+        });
+    };
+    Ok(Value::Bool(m.contains_key(&args[1])))
+}
+
+fn builtin_has_val(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    let Value::Map(m) = &args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "map",
+            got: args[0].type_name(),
+        });
+    };
+    Ok(Value::Bool(m.values().any(|v| *v == args[1])))
+}
+
+fn builtin_transient(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 1)?;
+    match &args[0] {
+        coll @ (Value::Vector(_) | Value::Map(_)) => {
+            Ok(Value::Transient(Rc::new(RefCell::new(Some(coll.clone())))))
+        }
+        other => Err(EvalError::TypeError {
+            expected: "vector or map",
+            got: other.type_name(),
+        }),
+    }
+}
+
+fn builtin_conj_mut(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 2)?;
+    with_transient("conj!", &args[0], |coll| match coll {
+        Value::Vector(v) => {
+            v.push(args[1].clone());
+            Ok(())
+        }
+        other => Err(EvalError::TypeError {
+            expected: "transient vector",
+            got: other.type_name(),
+        }),
+    })?;
+    Ok(args[0].clone())
+}
+
+fn builtin_assoc_mut(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 3)?;
+    with_transient("assoc!", &args[0], |coll| match coll {
+        Value::Map(m) => {
+            // The transient owns its map, so this only copies if `transient` was given a map that
+            // is still shared.
+            Rc::make_mut(m).insert(args[1].clone(), args[2].clone());
+            Ok(())
+        }
+        other => Err(EvalError::TypeError {
+            expected: "transient map",
+            got: other.type_name(),
+        }),
+    })?;
+    Ok(args[0].clone())
+}
+
+fn builtin_persistent(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    expect_arity(args, 1)?;
+    let Value::Transient(t) = &args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "transient",
+            got: args[0].type_name(),
+        });
+    };
+    t.borrow_mut()
+        .take()
+        .ok_or_else(|| persisted_error("persistent!"))
+}
+
+/// Runs `f` on the collection inside the transient `t`.
+fn with_transient(
+    form: &str,
+    t: &Value,
+    f: impl FnOnce(&mut Value) -> Result<(), EvalError>,
+) -> Result<(), EvalError> {
+    let Value::Transient(t) = t else {
+        return Err(EvalError::TypeError {
+            expected: "transient",
+            got: t.type_name(),
+        });
+    };
+    let mut coll = t.borrow_mut();
+    f(coll.as_mut().ok_or_else(|| persisted_error(form))?)
+}
+
+fn persisted_error(form: &str) -> EvalError {
+    EvalError::Custom(format!("{form}: transient used after persistent!"))
+}
+
 fn builtin_assert(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
     for a in args {
         if !a.is_truthy() {
@@ -623,10 +1826,36 @@ fn builtin_assert(args: &[Value], _env: &Rc<RefCell<Env>>) -> Result<Value, Eval
     Ok(Value::Nil)
 }
 
-fn string_for_io(v: &Value) -> String {
+/// Appends `item` the way `str` does: strings and chars raw, everything else as printed.
+fn push_str_value(out: &mut String, item: &Value) {
+    match item {
+        Value::String(s) => out.push_str(s),
+        Value::Char(c) => out.push(*c),
+        // Writing into a `String` can't fail.
+        _ => write!(out, "{item}").expect("write to String"),
+    }
+}
+
+/// Concatenates the I/O representation of `items` into a single pre-sized buffer.
+fn format_for_io(items: &[Value]) -> String {
+    let mut out = String::with_capacity(items.iter().map(io_len_hint).sum());
+    for item in items {
+        match item {
+            // I/O-oriented stringification: strings are raw (no quotes, no escaping).
+            Value::String(s) => out.push_str(s),
+            // Writing into a `String` can't fail.
+            _ => write!(out, "{item}").expect("write to String"),
+        }
+    }
+    out
+}
+
+/// Cheap estimate of how many bytes `v` takes when formatted for I/O.
+fn io_len_hint(v: &Value) -> usize {
     match v {
-        // I/O-oriented stringification: strings are raw (no quotes, no escaping).
-        Value::String(s) => s.clone(),
-        _ => v.to_string(),
+        Value::String(s) => s.len(),
+        Value::Int(i) => i.unsigned_abs().checked_ilog10().unwrap_or(0) as usize + 2,
+        Value::Char(_) | Value::Bool(_) | Value::Nil => 5,
+        _ => 16,
     }
 }
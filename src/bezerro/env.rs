@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
 use std::path::PathBuf;
 use std::rc::Rc;
 
@@ -12,25 +13,58 @@ pub struct ModuleInfo {
     pub mangle_map: HashMap<String, String>,
 }
 
+/// Dispatch function and method table of a multimethod (see `defmulti`/`defmethod`).
+#[derive(Debug, Clone)]
+pub struct Multimethod {
+    pub dispatch: Value,
+    pub methods: HashMap<Value, Value>,
+}
+
+/// Destination of `print`, `println` and `trace` output.
+#[derive(Debug, Clone, Default)]
+pub enum Output {
+    #[default]
+    Stdout,
+    Captured(Rc<RefCell<String>>),
+}
+
 #[derive(Debug, Clone)]
 pub struct Env {
     bindings: HashMap<String, Value>,
     parent: Option<Rc<RefCell<Env>>>,
+    /// Names a `let`/`loop` is still binding into this scope (see [`Env::set_pending`]).
+    pending: Vec<String>,
     source_dir: Option<PathBuf>,
     module_cache: Rc<RefCell<HashMap<PathBuf, ModuleInfo>>>,
     module_loading: Rc<RefCell<HashSet<PathBuf>>>,
+    multimethods: Rc<RefCell<HashMap<String, Multimethod>>>,
+    traced: Rc<RefCell<HashMap<String, Value>>>,
+    docs: Rc<RefCell<HashMap<String, String>>>,
+    imports: Rc<RefCell<HashMap<String, String>>>,
+    output: Rc<RefCell<Output>>,
 }
 
 impl Env {
     pub fn new() -> Self {
         let module_cache = Rc::new(RefCell::new(HashMap::new()));
         let module_loading = Rc::new(RefCell::new(HashSet::new()));
+        let multimethods = Rc::new(RefCell::new(HashMap::new()));
+        let traced = Rc::new(RefCell::new(HashMap::new()));
+        let docs = Rc::new(RefCell::new(HashMap::new()));
+        let imports = Rc::new(RefCell::new(HashMap::new()));
+        let output = Rc::new(RefCell::new(Output::Stdout));
         Env {
             bindings: HashMap::new(),
             parent: None,
+            pending: Vec::new(),
             source_dir: None,
             module_cache,
             module_loading,
+            multimethods,
+            traced,
+            docs,
+            imports,
+            output,
         }
     }
 
@@ -38,12 +72,23 @@ impl Env {
         let source_dir = parent.borrow().source_dir.clone();
         let module_cache = parent.borrow().module_cache.clone();
         let module_loading = parent.borrow().module_loading.clone();
+        let multimethods = parent.borrow().multimethods.clone();
+        let traced = parent.borrow().traced.clone();
+        let docs = parent.borrow().docs.clone();
+        let imports = parent.borrow().imports.clone();
+        let output = parent.borrow().output.clone();
         Env {
             bindings: HashMap::new(),
             parent: Some(parent),
+            pending: Vec::new(),
             source_dir,
             module_cache,
             module_loading,
+            multimethods,
+            traced,
+            docs,
+            imports,
+            output,
         }
     }
 
@@ -58,10 +103,19 @@ impl Env {
         self.parent.as_ref().and_then(|p| p.borrow().get(name))
     }
 
+    pub fn get_local(&self, name: &str) -> Option<Value> {
+        self.bindings.get(name).cloned()
+    }
+
     pub fn contains_local(&self, name: &str) -> bool {
         self.bindings.contains_key(name)
     }
 
+    /// The names bound directly in this scope, in no particular order.
+    pub fn local_names(&self) -> impl Iterator<Item = &str> {
+        self.bindings.keys().map(String::as_str)
+    }
+
     pub fn set(&mut self, name: &str, value: Value) -> Result<(), EvalError> {
         if self.bindings.contains_key(name) {
             self.bindings.insert(name.to_string(), value);
@@ -73,6 +127,17 @@ impl Env {
         Err(EvalError::UndefinedSymbol(name.to_string()))
     }
 
+    /// Records the names that will still be bound into this scope, in order. Closures created
+    /// while they are pending may refer to them (their own name, or a later sibling binding)
+    /// before they exist.
+    pub fn set_pending(&mut self, names: Vec<String>) {
+        self.pending = names;
+    }
+
+    pub fn is_pending(&self, name: &str) -> bool {
+        self.pending.iter().any(|n| n == name)
+    }
+
     pub fn parent(&self) -> Option<Rc<RefCell<Env>>> {
         self.parent.clone()
     }
@@ -96,6 +161,57 @@ impl Env {
     pub fn module_loading(&self) -> Rc<RefCell<HashSet<PathBuf>>> {
         self.module_loading.clone()
     }
+
+    /// Multimethod registry, created by the root env and shared with every child env.
+    pub fn multimethods(&self) -> Rc<RefCell<HashMap<String, Multimethod>>> {
+        self.multimethods.clone()
+    }
+
+    /// Original values of the functions currently instrumented by `trace`, keyed by name.
+    pub fn traced(&self) -> Rc<RefCell<HashMap<String, Value>>> {
+        self.traced.clone()
+    }
+
+    /// Docstrings of user definitions, keyed by the defined name.
+    pub fn docs(&self) -> Rc<RefCell<HashMap<String, String>>> {
+        self.docs.clone()
+    }
+
+    /// Mangled global that each name imported by `use` was copied from, keyed by the imported
+    /// name. The module's own code keeps referring to the mangled name.
+    pub fn imports(&self) -> Rc<RefCell<HashMap<String, String>>> {
+        self.imports.clone()
+    }
+
+    /// Redirects output of every env sharing this one's sink into the returned buffer.
+    pub fn capture_output(&self) -> Rc<RefCell<String>> {
+        let buffer = Rc::new(RefCell::new(String::new()));
+        *self.output.borrow_mut() = Output::Captured(buffer.clone());
+        buffer
+    }
+
+    /// Writes `text` to the output sink, flushing stdout.
+    pub fn write_output(&self, text: &str) -> Result<(), EvalError> {
+        match &*self.output.borrow() {
+            Output::Stdout => {
+                let mut stdout = io::stdout();
+                stdout
+                    .write_all(text.as_bytes())
+                    .and_then(|_| stdout.flush())
+                    .map_err(|e| EvalError::Custom(format!("stdout write failed: {e}")))
+            }
+            Output::Captured(buffer) => {
+                buffer.borrow_mut().push_str(text);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Default for Env {
+    fn default() -> Self {
+        Env::new()
+    }
 }
 
 pub fn define_global(env: &Rc<RefCell<Env>>, name: String, value: Value) {
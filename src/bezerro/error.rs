@@ -1,4 +1,7 @@
 use std::fmt;
+use std::path::PathBuf;
+
+use crate::vedn;
 
 #[derive(Debug, Clone)]
 pub enum UseError {
@@ -13,6 +16,8 @@ pub enum UseError {
     LastSegmentCannotBeSuper,
     SuperBeyondRoot { module: String },
     FailedToDetermineBaseDir,
+    ModuleNotFound { module: String, tried: Vec<String> },
+    DirectoryWithoutIndex { module: String, dir: String },
     ResolveFailed { path: String, error: String },
     ReadFailed { path: String, error: String },
     CyclicUse { path: String },
@@ -40,7 +45,12 @@ pub enum EvalError {
         len: usize,
     },
     NotCallable(&'static str),
-    ParseError(String),
+    /// Source text failed to parse. `path` names the file it came from, if any, so the error can
+    /// be rendered against that file's contents.
+    ParseError {
+        path: Option<PathBuf>,
+        error: vedn::Error,
+    },
     Use(UseError),
     Custom(String),
 }
@@ -80,6 +90,19 @@ impl fmt::Display for UseError {
             UseError::FailedToDetermineBaseDir => {
                 write!(f, "use: failed to determine base directory")
             }
+            UseError::ModuleNotFound { module, tried } => write!(
+                f,
+                "use: module `{module}` not found (tried {})",
+                tried
+                    .iter()
+                    .map(|p| format!("`{p}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            UseError::DirectoryWithoutIndex { module, dir } => write!(
+                f,
+                "use: `{module}` resolves to directory `{dir}`, which has no `mod.vaca`"
+            ),
             UseError::ResolveFailed { path, error } => {
                 write!(f, "use: failed to resolve module path `{path}`: {error}")
             }
@@ -113,7 +136,11 @@ impl fmt::Display for EvalError {
                 write!(f, "index out of bounds: {index} (len {len})")
             }
             EvalError::NotCallable(got) => write!(f, "value is not callable: {got}"),
-            EvalError::ParseError(s) => write!(f, "parse error: {s}"),
+            EvalError::ParseError {
+                path: Some(path),
+                error,
+            } => write!(f, "parse error in `{}`: {error}", path.display()),
+            EvalError::ParseError { path: None, error } => write!(f, "parse error: {error}"),
             EvalError::Use(e) => write!(f, "{e}"),
             EvalError::Custom(s) => write!(f, "{s}"),
         }
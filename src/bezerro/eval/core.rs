@@ -1,23 +1,51 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
 use std::rc::Rc;
 
-use crate::bezerro::env::Env;
+use crate::bezerro::env::{root_env, Env};
 use crate::bezerro::error::EvalError;
-use crate::bezerro::value::Value;
+use crate::bezerro::ordered_map::OrderedMap;
+use crate::bezerro::value::{intern, Value};
 use crate::vedn::{Kind, Node, Number};
 
 use super::special_forms::{
-    special_def, special_defmacro, special_defn, special_do, special_fn, special_if, special_let,
-    special_loop, special_pipe, special_quote, special_recur,
+    special_def, special_defmacro, special_defmethod, special_defmulti, special_defn, special_do,
+    special_doto, special_fn, special_help, special_if, special_let, special_loop, special_pipe,
+    special_quote, special_recur, special_trace, special_try, special_unless, special_untrace,
+    special_when, special_with_redefs,
 };
 use super::use_form::special_use;
 
 pub(super) const MAX_STACK_DEPTH: usize = 10_000;
 
 pub(super) const SPECIAL_FORM_HEADS: &[&str] = &[
-    "def", "defn", "fn", "if", "do", "let", "quote", "defmacro", "deftype", "use", "|>", "recur",
+    "def",
+    "def-",
+    "defn",
+    "defn-",
+    "fn",
+    "if",
+    "do",
+    "when",
+    "unless",
+    "doto",
+    "with-redefs",
+    "let",
+    "quote",
+    "defmacro",
+    "deftype",
+    "use",
+    "|>",
+    "recur",
     "loop",
+    "defmulti",
+    "defmethod",
+    "trace",
+    "untrace",
+    "help",
+    "try",
 ];
 
 pub(super) fn recur_tail_position_error() -> EvalError {
@@ -35,6 +63,27 @@ pub fn eval(node: &Node<'_>, env: &Rc<RefCell<Env>>) -> Result<Value, EvalError>
     Ok(out)
 }
 
+/// Evaluates every form of the file at `path` in `env` and returns the last value.
+///
+/// While the file runs, relative `use` paths resolve against its directory.
+pub fn eval_file(path: &Path, env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let src = fs::read_to_string(path)
+        .map_err(|e| EvalError::Custom(format!("failed to read {}: {e}", path.display())))?;
+    let nodes = crate::parse(&src).map_err(|error| EvalError::ParseError {
+        path: Some(path.to_path_buf()),
+        error,
+    })?;
+
+    let root = root_env(env);
+    let prev_source_dir = root.borrow().source_dir();
+    if let Some(dir) = path.parent() {
+        root.borrow_mut().set_source_dir(dir.to_path_buf());
+    }
+    let result = nodes.iter().try_fold(Value::Nil, |_, node| eval(node, env));
+    root.borrow_mut().set_source_dir_opt(prev_source_dir);
+    result
+}
+
 pub fn eval_value(form: &Value, env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
     let out = eval_value_impl(form, env, 0)?;
     if matches!(out, Value::Recur(_)) {
@@ -65,14 +114,18 @@ pub(super) fn eval_value_impl(
         | Value::String(_)
         | Value::Keyword(_)
         | Value::Builtin { .. }
+        | Value::Native { .. }
         | Value::Lambda { .. }
         | Value::Macro { .. }
+        | Value::Multimethod { .. }
+        | Value::LazySeq(_)
+        | Value::Transient(_)
         | Value::Recur(_) => Ok(form.clone()),
 
         Value::Symbol(name) => env
             .borrow()
             .get(name)
-            .ok_or_else(|| EvalError::UndefinedSymbol(name.clone())),
+            .ok_or_else(|| EvalError::UndefinedSymbol(name.to_string())),
 
         Value::Vector(items) => {
             let mut out = Vec::with_capacity(items.len());
@@ -86,6 +139,7 @@ pub(super) fn eval_value_impl(
             Ok(Value::Vector(out))
         }
         Value::Set(items) => {
+            #[allow(clippy::mutable_key_type)]
             let mut out = HashSet::with_capacity(items.len());
             for item in items.iter() {
                 let v = eval_value_impl(item, env, depth + 1)?;
@@ -97,7 +151,7 @@ pub(super) fn eval_value_impl(
             Ok(Value::Set(Rc::new(out)))
         }
         Value::Map(entries) => {
-            let mut out: HashMap<Value, Value> = HashMap::with_capacity(entries.len());
+            let mut out = OrderedMap::with_capacity(entries.len());
             for (k, v) in entries.iter() {
                 let kk = eval_value_impl(k, env, depth + 1)?;
                 if matches!(kk, Value::Recur(_)) {
@@ -127,12 +181,16 @@ fn eval_list_impl(
 
     // Special forms dispatch on the first element if it's a symbol.
     if let Value::Symbol(head) = &items[0] {
-        match head.as_str() {
-            "def" => return special_def(&items[1..], env, depth),
-            "defn" => return special_defn(&items[1..], env, depth),
+        match &**head {
+            "def" | "def-" => return special_def(&items[1..], env, depth),
+            "defn" | "defn-" => return special_defn(&items[1..], env, depth),
             "fn" => return special_fn(&items[1..], env, false),
             "if" => return special_if(&items[1..], env, depth),
             "do" => return special_do(&items[1..], env, depth),
+            "when" => return special_when(&items[1..], env, depth),
+            "unless" => return special_unless(&items[1..], env, depth),
+            "doto" => return special_doto(&items[1..], env, depth),
+            "with-redefs" => return special_with_redefs(&items[1..], env, depth),
             "let" => return special_let(&items[1..], env, depth),
             "quote" => return special_quote(&items[1..]),
             "defmacro" => return special_defmacro(&items[1..], env),
@@ -141,6 +199,12 @@ fn eval_list_impl(
             "|>" => return special_pipe(&items[1..], env, depth),
             "recur" => return special_recur(&items[1..], env, depth),
             "loop" => return special_loop(&items[1..], env, depth),
+            "defmulti" => return special_defmulti(&items[1..], env, depth),
+            "defmethod" => return special_defmethod(&items[1..], env, depth),
+            "trace" => return special_trace(&items[1..], env),
+            "untrace" => return special_untrace(&items[1..], env),
+            "help" => return special_help(&items[1..], env),
+            "try" => return special_try(&items[1..], env, depth),
             _ => {}
         }
     }
@@ -184,6 +248,7 @@ fn apply_impl(
 
     match func {
         Value::Builtin { func, .. } => func(args, env),
+        Value::Native { func, .. } => func(args, env),
         Value::Lambda {
             params,
             body,
@@ -218,10 +283,67 @@ fn apply_impl(
                 }
             }
         }
+        Value::Multimethod { name } => {
+            let method = select_method(name, args, env, depth)?;
+            apply_impl(&method, args, env, depth + 1)
+        }
+        // `(:field m)` / `(:field m default)`: field access on maps, which also serve as records.
+        Value::Keyword(_) => {
+            let (coll, default) = match args {
+                [coll] => (coll, Value::Nil),
+                [coll, default] => (coll, default.clone()),
+                _ => {
+                    return Err(EvalError::Custom(format!(
+                        "{func} expects: ({func} m) or ({func} m default)"
+                    )))
+                }
+            };
+            Ok(coll.lookup(func)?.unwrap_or(default))
+        }
         other => Err(EvalError::NotCallable(other.type_name())),
     }
 }
 
+/// Picks the method of multimethod `name` matching the dispatch value of `args`, falling back to
+/// the `:default` method.
+fn select_method(
+    name: &str,
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    let registry = env.borrow().multimethods();
+    let Some(dispatch) = registry.borrow().get(name).map(|m| m.dispatch.clone()) else {
+        return Err(EvalError::Custom(format!(
+            "multimethod `{name}` is not defined"
+        )));
+    };
+
+    // A keyword dispatch function looks itself up in the first argument, so
+    // `(defmulti area :shape)` dispatches on `(get shape :shape)`.
+    let dispatch_value = match (&dispatch, args.first()) {
+        (Value::Keyword(_), Some(first)) => first.lookup(&dispatch)?.unwrap_or(Value::Nil),
+        _ => apply_impl(&dispatch, args, env, depth + 1)?,
+    };
+
+    let registry = registry.borrow();
+    #[allow(clippy::mutable_key_type)]
+    let methods = &registry[name].methods;
+    let default = Value::Keyword(crate::bezerro::value::Keyword {
+        namespace: None,
+        name: intern("default"),
+    });
+    methods
+        .get(&dispatch_value)
+        .or_else(|| methods.get(&default))
+        .cloned()
+        .ok_or_else(|| {
+            EvalError::Custom(format!(
+                "multimethod `{name}` has no method for dispatch value {dispatch_value}"
+            ))
+        })
+}
+
 fn apply_macro(func: &Value, raw_args: &[Value], depth: usize) -> Result<Value, EvalError> {
     let Value::Macro {
         params,
@@ -271,10 +393,10 @@ pub fn node_to_form(node: &Node<'_>) -> Value {
         Kind::Char(c) => Value::Char(*c),
         Kind::String(s) => Value::String(s.as_str().to_string()),
         Kind::Keyword(k) => Value::Keyword(crate::bezerro::value::Keyword {
-            namespace: k.namespace.map(str::to_string),
-            name: k.name.to_string(),
+            namespace: k.namespace.map(intern),
+            name: intern(k.name),
         }),
-        Kind::Symbol(s) => Value::Symbol(s.raw.to_string()),
+        Kind::Symbol(s) => Value::Symbol(intern(s.raw)),
         Kind::Number(n) => number_to_value(n),
         Kind::List(items) => Value::List(items.iter().map(node_to_form).collect()),
         Kind::Vector(items) => Value::Vector(items.iter().map(node_to_form).collect()),
@@ -285,11 +407,44 @@ pub fn node_to_form(node: &Node<'_>) -> Value {
             entries
                 .iter()
                 .map(|(k, v)| (node_to_form(k), node_to_form(v)))
-                .collect::<HashMap<_, _>>(),
+                .collect::<OrderedMap>(),
         )),
     }
 }
 
+/// A form together with the annotations (`#<form> <form>`) it was read with.
+///
+/// [`node_to_form`] drops annotations; analysis passes that need declared types use
+/// [`node_to_annotated_form`] instead, which keeps them in a tree parallel to the form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotatedForm {
+    /// The form, exactly as [`node_to_form`] would produce it.
+    pub value: Value,
+    /// This node's annotation, converted to a form.
+    pub annotation: Option<Value>,
+    /// One entry per child node in source order. Map entries contribute their key, then their
+    /// value. Empty for atoms.
+    pub children: Vec<AnnotatedForm>,
+}
+
+pub fn node_to_annotated_form(node: &Node<'_>) -> AnnotatedForm {
+    let children = match &node.kind {
+        Kind::List(items) | Kind::Vector(items) | Kind::Set(items) => {
+            items.iter().map(node_to_annotated_form).collect()
+        }
+        Kind::Map(entries) => entries
+            .iter()
+            .flat_map(|(k, v)| [node_to_annotated_form(k), node_to_annotated_form(v)])
+            .collect(),
+        _ => Vec::new(),
+    };
+    AnnotatedForm {
+        value: node_to_form(node),
+        annotation: node.annotation.as_deref().map(node_to_form),
+        children,
+    }
+}
+
 pub(super) fn number_to_value(n: &Number<'_>) -> Value {
     match n {
         Number::Int { lexeme, .. } => lexeme
@@ -2,7 +2,9 @@ mod core;
 mod special_forms;
 mod use_form;
 
-pub use core::{apply, eval, eval_value, node_to_form};
+pub use core::{
+    apply, eval, eval_file, eval_value, node_to_annotated_form, node_to_form, AnnotatedForm,
+};
 
 #[cfg(test)]
 mod tests;
@@ -1,13 +1,15 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use std::rc::Rc;
 
-use crate::bezerro::env::define_global;
-use crate::bezerro::env::Env;
+use crate::bezerro::builtins::help_text;
+use crate::bezerro::env::{define_global, root_env};
+use crate::bezerro::env::{Env, Multimethod};
 use crate::bezerro::error::EvalError;
-use crate::bezerro::value::Value;
+use crate::bezerro::value::{intern, Value};
 
 use super::core::MAX_STACK_DEPTH;
-use super::core::{eval_do_forms_impl, eval_value_impl, recur_tail_position_error};
+use super::core::{apply, eval_do_forms_impl, eval_value_impl, recur_tail_position_error};
 
 pub(super) fn special_def(
     args: &[Value],
@@ -30,7 +32,7 @@ pub(super) fn special_def(
     if matches!(value, Value::Recur(_)) {
         return Err(recur_tail_position_error());
     }
-    define_global(env, name.clone(), value.clone());
+    define_global(env, name.to_string(), value.clone());
     Ok(value)
 }
 
@@ -39,9 +41,13 @@ pub(super) fn special_defn(
     env: &Rc<RefCell<Env>>,
     _depth: usize,
 ) -> Result<Value, EvalError> {
-    if args.len() < 3 {
+    let (doc, fn_args) = match args.get(1) {
+        Some(Value::String(doc)) => (Some(doc), args.get(2..).unwrap_or_default()),
+        _ => (None, args.get(1..).unwrap_or_default()),
+    };
+    if fn_args.len() < 2 {
         return Err(EvalError::Custom(
-            "defn expects: (defn name [params] body...)".to_string(),
+            "defn expects: (defn name \"doc\"? [params] body...)".to_string(),
         ));
     }
     let Value::Symbol(name) = &args[0] else {
@@ -50,8 +56,13 @@ pub(super) fn special_defn(
             got: args[0].type_name(),
         });
     };
-    let lambda = special_fn(&args[1..], env, true)?;
-    define_global(env, name.clone(), lambda.clone());
+    let lambda = special_fn(fn_args, env, true)?;
+    define_global(env, name.to_string(), lambda.clone());
+    let docs = env.borrow().docs();
+    match doc {
+        Some(doc) => docs.borrow_mut().insert(name.to_string(), doc.clone()),
+        None => docs.borrow_mut().remove(&**name),
+    };
     Ok(lambda)
 }
 
@@ -67,11 +78,107 @@ pub(super) fn special_fn(
     }
     let params = parse_params(&args[0])?;
     let body = args[1..].to_vec();
-    Ok(Value::Lambda {
-        params,
-        body,
-        env: env.clone(),
-    })
+    let env = capture_env(&params, &body, env);
+    Ok(Value::Lambda { params, body, env })
+}
+
+/// Builds the environment a new closure captures.
+///
+/// Instead of holding on to the whole enclosing scope chain (and every large binding in it), the
+/// closure gets a fresh scope under the root env holding only the local bindings its body may
+/// reference. The scan is purely syntactic and over-approximates (any symbol in the body counts),
+/// so capture is always complete. Two cases fall back to capturing `env` as-is: bodies that
+/// mention a macro, since an expansion can reference names that don't appear in the body, and
+/// bodies that mention a name an enclosing `let`/`loop` hasn't bound yet (a recursive local
+/// function, or one calling a later sibling), since there is no value to copy yet.
+fn capture_env(params: &[String], body: &[Value], env: &Rc<RefCell<Env>>) -> Rc<RefCell<Env>> {
+    let root = root_env(env);
+    if Rc::ptr_eq(&root, env) {
+        return env.clone();
+    }
+
+    let mut symbols = HashSet::new();
+    for form in body {
+        collect_symbols(form, &mut symbols);
+    }
+
+    let mut captured = Vec::new();
+    for name in symbols {
+        if params.iter().any(|p| p == name) {
+            continue;
+        }
+        match lookup_local(env, name) {
+            Some(Value::Macro { .. }) => return env.clone(),
+            Some(value) => captured.push((name.to_string(), value)),
+            None => {
+                if is_pending_local(env, name)
+                    || matches!(root.borrow().get_local(name), Some(Value::Macro { .. }))
+                {
+                    return env.clone();
+                }
+            }
+        }
+    }
+
+    let closure_env = Rc::new(RefCell::new(Env::with_parent(root)));
+    closure_env
+        .borrow_mut()
+        .set_source_dir_opt(env.borrow().source_dir());
+    for (name, value) in captured {
+        closure_env.borrow_mut().define(name, value);
+    }
+    closure_env
+}
+
+/// Looks `name` up in the scopes between `env` and the root env (exclusive).
+fn lookup_local(env: &Rc<RefCell<Env>>, name: &str) -> Option<Value> {
+    let mut cur = env.clone();
+    loop {
+        let parent = cur.borrow().parent()?;
+        if let Some(value) = cur.borrow().get_local(name) {
+            return Some(value);
+        }
+        cur = parent;
+    }
+}
+
+/// Whether a `let`/`loop` between `env` and the root env has yet to bind `name`.
+fn is_pending_local(env: &Rc<RefCell<Env>>, name: &str) -> bool {
+    let mut cur = env.clone();
+    loop {
+        let Some(parent) = cur.borrow().parent() else {
+            return false;
+        };
+        if cur.borrow().is_pending(name) {
+            return true;
+        }
+        cur = parent;
+    }
+}
+
+fn collect_symbols<'v>(form: &'v Value, out: &mut HashSet<&'v str>) {
+    match form {
+        Value::Symbol(name) => {
+            out.insert(name);
+        }
+        Value::List(items) | Value::Vector(items) => {
+            for item in items {
+                collect_symbols(item, out);
+            }
+        }
+        Value::Set(items) => {
+            for item in items.iter() {
+                collect_symbols(item, out);
+            }
+        }
+        Value::Map(entries) => {
+            for (k, v) in entries.iter() {
+                collect_symbols(k, out);
+                collect_symbols(v, out);
+            }
+        }
+        _ => {}
+    }
 }
 
 pub(super) fn special_defmacro(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
@@ -93,10 +200,233 @@ pub(super) fn special_defmacro(args: &[Value], env: &Rc<RefCell<Env>>) -> Result
         body,
         env: env.clone(),
     };
-    define_global(env, name.clone(), mac.clone());
+    define_global(env, name.to_string(), mac.clone());
     Ok(mac)
 }
 
+pub(super) fn special_defmulti(
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    if args.len() != 2 {
+        return Err(EvalError::Custom(
+            "defmulti expects: (defmulti name dispatch-fn)".to_string(),
+        ));
+    }
+    let Value::Symbol(name) = &args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "symbol",
+            got: args[0].type_name(),
+        });
+    };
+    let dispatch = eval_value_impl(&args[1], env, depth + 1)?;
+    if matches!(dispatch, Value::Recur(_)) {
+        return Err(recur_tail_position_error());
+    }
+
+    // Redefining a multimethod swaps its dispatch function but keeps the registered methods.
+    let registry = env.borrow().multimethods();
+    registry
+        .borrow_mut()
+        .entry(name.to_string())
+        .and_modify(|m| m.dispatch = dispatch.clone())
+        .or_insert_with(|| Multimethod {
+            dispatch,
+            methods: Default::default(),
+        });
+
+    let multi = Value::Multimethod {
+        name: name.to_string(),
+    };
+    define_global(env, name.to_string(), multi.clone());
+    Ok(multi)
+}
+
+pub(super) fn special_defmethod(
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    if args.len() < 4 {
+        return Err(EvalError::Custom(
+            "defmethod expects: (defmethod name dispatch-value [params] body...)".to_string(),
+        ));
+    }
+    let Value::Symbol(name) = &args[0] else {
+        return Err(EvalError::TypeError {
+            expected: "symbol",
+            got: args[0].type_name(),
+        });
+    };
+    let dispatch_value = eval_value_impl(&args[1], env, depth + 1)?;
+    if matches!(dispatch_value, Value::Recur(_)) {
+        return Err(recur_tail_position_error());
+    }
+    let method = special_fn(&args[2..], env, false)?;
+
+    // Resolve through the binding so methods can be added via an imported alias.
+    let Some(Value::Multimethod { name: key }) = env.borrow().get(name) else {
+        return Err(EvalError::Custom(format!(
+            "defmethod: `{name}` is not a multimethod"
+        )));
+    };
+    let registry = env.borrow().multimethods();
+    let mut registry = registry.borrow_mut();
+    let Some(multi) = registry.get_mut(&key) else {
+        return Err(EvalError::Custom(format!(
+            "defmethod: `{name}` is not a multimethod"
+        )));
+    };
+    multi.methods.insert(dispatch_value, method.clone());
+    Ok(method)
+}
+
+/// `(trace name)`: rebinds the global function `name` to a wrapper that prints every call and
+/// its return value, indented by call depth. Recursive calls go through the wrapper too.
+pub(super) fn special_trace(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let name = traced_name("trace", args)?;
+    let traced = env.borrow().traced();
+    if traced.borrow().contains_key(name) {
+        return Ok(Value::Nil);
+    }
+    let root = root_env(env);
+    let original = root
+        .borrow()
+        .get_local(name)
+        .ok_or_else(|| EvalError::UndefinedSymbol(name.to_string()))?;
+    if !matches!(
+        original,
+        Value::Builtin { .. }
+            | Value::Native { .. }
+            | Value::Lambda { .. }
+            | Value::Multimethod { .. }
+    ) {
+        return Err(EvalError::TypeError {
+            expected: "function",
+            got: original.type_name(),
+        });
+    }
+
+    let call_depth = Rc::new(Cell::new(0usize));
+    let (fname, inner) = (intern(name), original.clone());
+    let wrapper = Value::Native {
+        name: name.to_string(),
+        func: Rc::new(move |args, env| {
+            let indent = "  ".repeat(call_depth.get());
+            let call = std::iter::once(Value::Symbol(fname.clone()))
+                .chain(args.iter().cloned())
+                .collect();
+            env.borrow()
+                .write_output(&format!("{indent}{}\n", Value::List(call)))?;
+
+            call_depth.set(call_depth.get() + 1);
+            let result = apply(&inner, args, env);
+            call_depth.set(call_depth.get() - 1);
+
+            let result = result?;
+            env.borrow()
+                .write_output(&format!("{indent}=> {result}\n"))?;
+            Ok(result)
+        }),
+    };
+    traced.borrow_mut().insert(name.to_string(), original);
+    define_global(env, name.to_string(), wrapper);
+    Ok(Value::Nil)
+}
+
+/// `(untrace name)`: restores the function instrumented by `trace`. No-op if it isn't traced.
+pub(super) fn special_untrace(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let name = traced_name("untrace", args)?;
+    let original = env.borrow().traced().borrow_mut().remove(name);
+    if let Some(original) = original {
+        define_global(env, name.to_string(), original);
+    }
+    Ok(Value::Nil)
+}
+
+/// `(with-redefs [name value ...] body...)`: rebinds existing globals while `body` runs and
+/// restores them afterwards, also when `body` fails. Values are evaluated before any rebinding.
+///
+/// A name imported by `use` is rebound together with the module's mangled global it was copied
+/// from, so the module's own functions see the new value too.
+pub(super) fn special_with_redefs(
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    let Some((Value::Vector(bindings), body)) = args.split_first() else {
+        return Err(EvalError::Custom(
+            "with-redefs expects: (with-redefs [name value ...] body...)".to_string(),
+        ));
+    };
+    if bindings.len() % 2 != 0 {
+        return Err(EvalError::Custom(
+            "with-redefs expects an even number of forms in its bindings".to_string(),
+        ));
+    }
+
+    let root = root_env(env);
+    let imports = root.borrow().imports();
+    let mut saved = Vec::with_capacity(bindings.len() / 2);
+    let mut redefs = Vec::with_capacity(bindings.len() / 2);
+    for pair in bindings.chunks(2) {
+        let Value::Symbol(name) = &pair[0] else {
+            return Err(EvalError::TypeError {
+                expected: "symbol",
+                got: pair[0].type_name(),
+            });
+        };
+        let mangled = imports.borrow().get(&**name).cloned();
+        let targets: Vec<String> = std::iter::once(name.to_string()).chain(mangled).collect();
+        for target in &targets {
+            let Some(original) = root.borrow().get_local(target) else {
+                return Err(EvalError::UndefinedSymbol(target.clone()));
+            };
+            saved.push((target.clone(), original));
+        }
+        let value = eval_value_impl(&pair[1], env, depth + 1)?;
+        if matches!(value, Value::Recur(_)) {
+            return Err(recur_tail_position_error());
+        }
+        redefs.push((targets, value));
+    }
+
+    for (targets, value) in redefs {
+        for target in targets {
+            define_global(env, target, value.clone());
+        }
+    }
+    let result = eval_do_forms_impl(body, env, depth + 1);
+    // Reverse order, so a name bound twice ends up with its value from before the form.
+    for (name, original) in saved.into_iter().rev() {
+        define_global(env, name, original);
+    }
+
+    let result = result?;
+    if matches!(result, Value::Recur(_)) {
+        return Err(recur_tail_position_error());
+    }
+    Ok(result)
+}
+
+/// `(help name)`: prints the signature and docstring of `name`.
+pub(super) fn special_help(args: &[Value], env: &Rc<RefCell<Env>>) -> Result<Value, EvalError> {
+    let [Value::Symbol(name)] = args else {
+        return Err(EvalError::Custom("help expects: (help name)".to_string()));
+    };
+    let text = help_text(name, env).ok_or_else(|| EvalError::UndefinedSymbol(name.to_string()))?;
+    env.borrow().write_output(&format!("{text}\n"))?;
+    Ok(Value::Nil)
+}
+
+fn traced_name<'v>(form: &str, args: &'v [Value]) -> Result<&'v str, EvalError> {
+    let [Value::Symbol(name)] = args else {
+        return Err(EvalError::Custom(format!("{form} expects: ({form} name)")));
+    };
+    Ok(name)
+}
+
 fn parse_params(form: &Value) -> Result<Vec<String>, EvalError> {
     let Value::Vector(items) = form else {
         return Err(EvalError::TypeError {
@@ -112,7 +442,7 @@ fn parse_params(form: &Value) -> Result<Vec<String>, EvalError> {
                 got: item.type_name(),
             });
         };
-        out.push(name.clone());
+        out.push(name.to_string());
     }
     Ok(out)
 }
@@ -147,47 +477,239 @@ pub(super) fn special_do(
     eval_do_forms_impl(args, env, depth + 1)
 }
 
-pub(super) fn special_let(
+/// `(when test body...)`: evaluates `body` as in `do` if `test` is truthy, otherwise returns nil.
+pub(super) fn special_when(
     args: &[Value],
     env: &Rc<RefCell<Env>>,
     depth: usize,
 ) -> Result<Value, EvalError> {
-    if args.len() < 2 {
+    eval_conditional_body("when", true, args, env, depth)
+}
+
+/// `(unless test body...)`: the negation of `when`; `body` runs only if `test` is falsy.
+pub(super) fn special_unless(
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    eval_conditional_body("unless", false, args, env, depth)
+}
+
+fn eval_conditional_body(
+    form: &str,
+    run_when: bool,
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    let Some((test, body)) = args.split_first() else {
+        return Err(EvalError::Custom(format!(
+            "{form} expects: ({form} test body...)"
+        )));
+    };
+    let cond = eval_value_impl(test, env, depth + 1)?;
+    if matches!(cond, Value::Recur(_)) {
+        return Err(recur_tail_position_error());
+    }
+    if cond.is_truthy() == run_when {
+        eval_do_forms_impl(body, env, depth + 1)
+    } else {
+        Ok(Value::Nil)
+    }
+}
+
+/// `(doto x (f a...) g...)`: evaluates `x`, calls each form with `x` inserted as its first
+/// argument (a bare `g` becomes `(g x)`), and returns `x`.
+pub(super) fn special_doto(
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    let Some((target, forms)) = args.split_first() else {
         return Err(EvalError::Custom(
-            "let expects: (let {name value ...} body...)".to_string(),
+            "doto expects: (doto x forms...)".to_string(),
         ));
-    }
-    let Value::Map(bindings) = &args[0] else {
-        return Err(EvalError::TypeError {
-            expected: "map",
-            got: args[0].type_name(),
-        });
     };
+    let target = eval_value_impl(target, env, depth + 1)?;
+    if matches!(target, Value::Recur(_)) {
+        return Err(recur_tail_position_error());
+    }
 
-    let new_env = Rc::new(RefCell::new(Env::with_parent(env.clone())));
-    let mut evaluated = Vec::with_capacity(bindings.len());
-    for (k, v) in bindings.iter() {
-        let Value::Symbol(name) = k else {
-            return Err(EvalError::TypeError {
-                expected: "symbol",
-                got: k.type_name(),
-            });
+    // Quote the value so it's passed as-is instead of being evaluated again as a form.
+    let quoted = Value::List(vec![Value::Symbol(intern("quote")), target.clone()]);
+    for form in forms {
+        let call = match form {
+            Value::List(items) if !items.is_empty() => {
+                let mut call = Vec::with_capacity(items.len() + 1);
+                call.push(items[0].clone());
+                call.push(quoted.clone());
+                call.extend_from_slice(&items[1..]);
+                call
+            }
+            other => vec![other.clone(), quoted.clone()],
         };
-        // Map bindings are evaluated in the *outer* environment so their behavior
-        // doesn't depend on map iteration order.
-        let value = eval_value_impl(v, env, depth + 1)?;
-        if matches!(value, Value::Recur(_)) {
+        let result = eval_value_impl(&Value::List(call), env, depth + 1)?;
+        if matches!(result, Value::Recur(_)) {
             return Err(recur_tail_position_error());
         }
-        evaluated.push((name.clone(), value));
     }
+    Ok(target)
+}
 
-    for (name, value) in evaluated {
-        new_env.borrow_mut().define(name, value);
+pub(super) fn special_let(
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    if args.len() < 2 {
+        return Err(EvalError::Custom(
+            "let expects: (let [name value ...] body...) or (let {name value ...} body...)"
+                .to_string(),
+        ));
     }
+
+    let new_env = Rc::new(RefCell::new(Env::with_parent(env.clone())));
+    eval_bindings("let", &args[0], env, &new_env, depth)?;
     eval_do_forms_impl(&args[1..], &new_env, depth + 1)
 }
 
+/// Evaluates the bindings of `let`/`loop` into `scope` and returns the bound names in the order
+/// `recur` rebinds them.
+///
+/// Vector bindings are sequential: each value is evaluated in `scope`, so it can refer to the
+/// names bound before it. Map bindings are parallel: every value is evaluated in the outer `env`.
+/// Either way the names come back in the order they were written.
+fn eval_bindings(
+    form: &str,
+    bindings: &Value,
+    env: &Rc<RefCell<Env>>,
+    scope: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Vec<String>, EvalError> {
+    match bindings {
+        Value::Vector(bindings) => {
+            if !bindings.len().is_multiple_of(2) {
+                return Err(EvalError::Custom(format!(
+                    "{form} bindings must have even number of forms"
+                )));
+            }
+            let mut names = Vec::with_capacity(bindings.len() / 2);
+            for pair in bindings.chunks(2) {
+                let Value::Symbol(name) = &pair[0] else {
+                    return Err(EvalError::TypeError {
+                        expected: "symbol",
+                        got: pair[0].type_name(),
+                    });
+                };
+                names.push(name.to_string());
+            }
+
+            for (i, pair) in bindings.chunks(2).enumerate() {
+                scope.borrow_mut().set_pending(names[i..].to_vec());
+                let value = eval_value_impl(&pair[1], scope, depth + 1);
+                scope.borrow_mut().set_pending(Vec::new());
+                let value = value?;
+                if matches!(value, Value::Recur(_)) {
+                    return Err(recur_tail_position_error());
+                }
+                scope.borrow_mut().define(names[i].clone(), value);
+            }
+            Ok(names)
+        }
+        Value::Map(bindings) => {
+            let mut pairs = Vec::with_capacity(bindings.len());
+            for (k, v) in bindings.iter() {
+                let Value::Symbol(name) = k else {
+                    return Err(EvalError::TypeError {
+                        expected: "symbol",
+                        got: k.type_name(),
+                    });
+                };
+                pairs.push((&**name, v));
+            }
+
+            let mut evaluated = Vec::with_capacity(pairs.len());
+            for &(name, v) in &pairs {
+                // Map bindings are parallel, so a binding that mentions a sibling binder can't mean
+                // "the value bound before it". Reject it instead of silently reading the outer one.
+                if let Some((sibling, _)) = pairs
+                    .iter()
+                    .find(|(n, _)| *n != name && mentions_symbol(v, n))
+                {
+                    return Err(EvalError::Custom(format!(
+                        "{form}: map bindings are parallel, `{name}` cannot refer to `{sibling}`; \
+                         use vector bindings for sequential {form}"
+                    )));
+                }
+                let value = eval_value_impl(v, env, depth + 1)?;
+                if matches!(value, Value::Recur(_)) {
+                    return Err(recur_tail_position_error());
+                }
+                evaluated.push((name.to_string(), value));
+            }
+
+            let mut names = Vec::with_capacity(evaluated.len());
+            for (name, value) in evaluated {
+                scope.borrow_mut().define(name.clone(), value);
+                names.push(name);
+            }
+            Ok(names)
+        }
+        other => Err(EvalError::TypeError {
+            expected: "vector or map",
+            got: other.type_name(),
+        }),
+    }
+}
+
+/// Returns whether `form` refers to `name`: mentions it outside of `quote` forms and outside the
+/// scope of a `fn` parameter or `let`/`loop` binder that rebinds it.
+fn mentions_symbol(form: &Value, name: &str) -> bool {
+    let binds = |binder: &Value| matches!(binder, Value::Symbol(s) if &**s == name);
+    match form {
+        Value::Symbol(s) => &**s == name,
+        Value::List(items) => match items.first() {
+            Some(Value::Symbol(head)) if &**head == "quote" => false,
+            Some(Value::Symbol(head)) if &**head == "fn" && items.len() >= 2 => {
+                let shadowed =
+                    matches!(&items[1], Value::Vector(params) if params.iter().any(binds));
+                !shadowed && items[2..].iter().any(|item| mentions_symbol(item, name))
+            }
+            Some(Value::Symbol(head)) if matches!(&**head, "let" | "loop") && items.len() >= 2 => {
+                let body = &items[2..];
+                match &items[1] {
+                    // Sequential: a binder hides `name` from the values after it and the body.
+                    Value::Vector(bindings) => {
+                        for pair in bindings.chunks(2) {
+                            if pair.get(1).is_some_and(|v| mentions_symbol(v, name)) {
+                                return true;
+                            }
+                            if binds(&pair[0]) {
+                                return false;
+                            }
+                        }
+                        body.iter().any(|item| mentions_symbol(item, name))
+                    }
+                    // Parallel: every value sees the outer `name`, only the body is shadowed.
+                    Value::Map(bindings) => {
+                        bindings.values().any(|v| mentions_symbol(v, name))
+                            || (!bindings.keys().any(binds)
+                                && body.iter().any(|item| mentions_symbol(item, name)))
+                    }
+                    _ => items.iter().any(|item| mentions_symbol(item, name)),
+                }
+            }
+            _ => items.iter().any(|item| mentions_symbol(item, name)),
+        },
+        Value::Vector(items) => items.iter().any(|item| mentions_symbol(item, name)),
+        Value::Set(items) => items.iter().any(|item| mentions_symbol(item, name)),
+        Value::Map(entries) => entries
+            .iter()
+            .any(|(k, v)| mentions_symbol(k, name) || mentions_symbol(v, name)),
+        _ => false,
+    }
+}
+
 pub(super) fn special_quote(args: &[Value]) -> Result<Value, EvalError> {
     if args.len() != 1 {
         return Err(EvalError::ArityError {
@@ -252,39 +774,13 @@ pub(super) fn special_loop(
 ) -> Result<Value, EvalError> {
     if args.len() < 2 {
         return Err(EvalError::Custom(
-            "loop expects: (loop [name value ...] body...)".to_string(),
-        ));
-    }
-
-    let Value::Vector(bindings) = &args[0] else {
-        return Err(EvalError::TypeError {
-            expected: "vector",
-            got: args[0].type_name(),
-        });
-    };
-    if bindings.len() % 2 != 0 {
-        return Err(EvalError::Custom(
-            "loop bindings must have even number of forms".to_string(),
+            "loop expects: (loop [name value ...] body...) or (loop {name value ...} body...)"
+                .to_string(),
         ));
     }
 
     let loop_env = Rc::new(RefCell::new(Env::with_parent(env.clone())));
-    let mut names: Vec<String> = Vec::with_capacity(bindings.len() / 2);
-
-    for pair in bindings.chunks(2) {
-        let Value::Symbol(name) = &pair[0] else {
-            return Err(EvalError::TypeError {
-                expected: "symbol",
-                got: pair[0].type_name(),
-            });
-        };
-        let value = eval_value_impl(&pair[1], &loop_env, depth + 1)?;
-        if matches!(value, Value::Recur(_)) {
-            return Err(recur_tail_position_error());
-        }
-        loop_env.borrow_mut().define(name.clone(), value);
-        names.push(name.clone());
-    }
+    let names = eval_bindings("loop", &args[0], env, &loop_env, depth)?;
 
     loop {
         if depth > MAX_STACK_DEPTH {
@@ -302,7 +798,7 @@ pub(super) fn special_loop(
                         got: new_vals.len(),
                     });
                 }
-                for (name, value) in names.iter().zip(new_vals.into_iter()) {
+                for (name, value) in names.iter().zip(new_vals) {
                     loop_env.borrow_mut().define(name.clone(), value);
                 }
             }
@@ -310,3 +806,60 @@ pub(super) fn special_loop(
         }
     }
 }
+
+/// `(try body... (catch e handler...))`
+///
+/// Evaluates `body`; if it fails, binds the error message to `e` and evaluates `handler`
+/// instead. Without a `catch` clause, `try` behaves like `do`.
+///
+/// A `recur` never crosses a `try`: jumping out of the body would silently skip the handler, so
+/// a `recur` in tail position of the body or handler is reported as a tail position error.
+pub(super) fn special_try(
+    args: &[Value],
+    env: &Rc<RefCell<Env>>,
+    depth: usize,
+) -> Result<Value, EvalError> {
+    let (body, handler) = match args.last() {
+        Some(Value::List(clause)) if matches!(clause.first(), Some(Value::Symbol(head)) if &**head == "catch") => {
+            (&args[..args.len() - 1], Some(clause))
+        }
+        _ => (args, None),
+    };
+
+    let handler = match handler {
+        Some(clause) => {
+            if clause.len() < 3 {
+                return Err(EvalError::Custom(
+                    "catch expects: (catch name body...)".to_string(),
+                ));
+            }
+            let Value::Symbol(name) = &clause[1] else {
+                return Err(EvalError::TypeError {
+                    expected: "symbol",
+                    got: clause[1].type_name(),
+                });
+            };
+            Some((name, &clause[2..]))
+        }
+        None => None,
+    };
+
+    let err = match eval_do_forms_impl(body, env, depth + 1) {
+        Ok(Value::Recur(_)) => return Err(recur_tail_position_error()),
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+    let Some((name, forms)) = handler else {
+        return Err(err);
+    };
+
+    let catch_env = Rc::new(RefCell::new(Env::with_parent(env.clone())));
+    catch_env
+        .borrow_mut()
+        .define(name.to_string(), Value::String(err.to_string()));
+    let value = eval_do_forms_impl(forms, &catch_env, depth + 1)?;
+    if matches!(value, Value::Recur(_)) {
+        return Err(recur_tail_position_error());
+    }
+    Ok(value)
+}
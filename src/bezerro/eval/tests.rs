@@ -76,12 +76,236 @@ fn keyword_equality_preserves_namespace_vs_slash_in_name() {
     assert_eq!(v, "false");
 }
 
+#[test]
+fn annotated_form_keeps_annotations() {
+    let nodes = crate::parse("#int 1").unwrap();
+    let form = node_to_annotated_form(&nodes[0]);
+    assert_eq!(form.value, Value::Int(1));
+    assert_eq!(form.annotation, Some(Value::Symbol("int".into())));
+    assert!(form.children.is_empty());
+    assert_eq!(form.value, node_to_form(&nodes[0]));
+}
+
+#[test]
+fn annotated_form_tree_parallels_collections() {
+    let nodes = crate::parse("(defn #int sum [#int a b] (+ a b))").unwrap();
+    let form = node_to_annotated_form(&nodes[0]);
+    assert_eq!(form.annotation, None);
+    assert_eq!(form.children.len(), 4);
+
+    let name = &form.children[1];
+    assert_eq!(name.value, Value::Symbol("sum".into()));
+    assert_eq!(name.annotation, Some(Value::Symbol("int".into())));
+
+    let params = &form.children[2];
+    assert_eq!(
+        params.children[0].annotation,
+        Some(Value::Symbol("int".into()))
+    );
+    assert_eq!(params.children[1].annotation, None);
+}
+
+#[test]
+fn symbols_and_keywords_share_interned_names() {
+    let nodes = crate::parse("(f :ns/k) (f :ns/k)").unwrap();
+    let (Value::List(a), Value::List(b)) = (node_to_form(&nodes[0]), node_to_form(&nodes[1]))
+    else {
+        panic!("expected lists");
+    };
+    let (Value::Symbol(f1), Value::Symbol(f2)) = (&a[0], &b[0]) else {
+        panic!("expected symbols");
+    };
+    assert!(Rc::ptr_eq(f1, f2));
+    let (Value::Keyword(k1), Value::Keyword(k2)) = (&a[1], &b[1]) else {
+        panic!("expected keywords");
+    };
+    assert!(Rc::ptr_eq(&k1.name, &k2.name));
+    assert!(Rc::ptr_eq(
+        k1.namespace.as_ref().unwrap(),
+        k2.namespace.as_ref().unwrap()
+    ));
+
+    // Equality and hashing go by name, not by allocation.
+    let fresh = Value::Symbol(Rc::from("f"));
+    assert_eq!(a[0], fresh);
+    #[allow(clippy::mutable_key_type)]
+    let set: std::collections::HashSet<_> = [a[0].clone()].into_iter().collect();
+    assert!(set.contains(&fresh));
+    assert_eq!(a[0].to_string(), "f");
+    assert_eq!(a[1].to_string(), ":ns/k");
+}
+
+#[test]
+fn many_symbol_references_evaluate() {
+    let mut src = String::new();
+    for i in 0..500 {
+        src.push_str(&format!("(def x{i} {i})\n"));
+    }
+    src.push_str("(+");
+    for i in 0..500 {
+        src.push_str(&format!(" x{i}"));
+    }
+    src.push(')');
+    assert_eq!(eval_program(&src).unwrap(), "124750");
+}
+
+#[test]
+fn format_matches_naive_concatenation() {
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    let format = env.borrow().get("format").unwrap();
+
+    let mut items: Vec<Value> = (-5_000..5_000).map(Value::Int).collect();
+    items.extend([
+        Value::String("raw \"text\"\n".to_string()),
+        Value::Float(0.1 + 0.2),
+        Value::Char('x'),
+        Value::Nil,
+        Value::Vector(vec![Value::String("quoted".to_string())]),
+    ]);
+    let naive: String = items
+        .iter()
+        .map(|v| match v {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+        .collect();
+
+    let from_vector = apply(&format, &[Value::Vector(items.clone())], &env).unwrap();
+    assert_eq!(from_vector, Value::String(naive.clone()));
+    let from_args = apply(&format, &items, &env).unwrap();
+    assert_eq!(from_args, Value::String(naive));
+}
+
+#[test]
+fn read_all_string_returns_every_form_as_data() {
+    let v = eval_program(r#"(read-all-string "(+ 1 2) [a b] ; comment\n:k")"#).unwrap();
+    assert_eq!(v, "[(+ 1 2) [a b] :k]");
+
+    let v = eval_program(r#"(read-all-string "  ; nothing here\n ")"#).unwrap();
+    assert_eq!(v, "[]");
+    let v = eval_program(r#"(read-all-string "")"#).unwrap();
+    assert_eq!(v, "[]");
+
+    let err = eval_program(r#"(read-all-string "(unclosed")"#).unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::ParseError { path: None, .. }
+    ));
+}
+
+#[test]
+fn nesting_limit_applies_to_read_all_string_but_not_source() {
+    let depth = crate::vedn::DEFAULT_MAX_DEPTH + 1;
+    let nested = "[".repeat(depth) + &"]".repeat(depth);
+    assert_eq!(eval_program(&nested).unwrap(), nested);
+
+    let err = eval_program(&format!("(read-all-string \"{nested}\")")).unwrap_err();
+    let crate::bezerro::error::EvalError::ParseError { error, .. } = err else {
+        panic!("expected a parse error, got {err:?}");
+    };
+    assert!(matches!(
+        error.kind,
+        crate::ErrorKind::NestingTooDeep { .. }
+    ));
+}
+
 #[test]
 fn let_uses_map_bindings() {
     let v = eval_program("(let {x 10 y 32} (+ x y))").unwrap();
     assert_eq!(v, "42");
 }
 
+#[test]
+fn let_uses_vector_bindings() {
+    let v = eval_program("(let [x 10 y 32] (+ x y))").unwrap();
+    assert_eq!(v, "42");
+}
+
+#[test]
+fn let_vector_bindings_are_sequential() {
+    let v = eval_program("(let [x 1 y (+ x 1) z (* y 10)] [x y z])").unwrap();
+    assert_eq!(v, "[1 2 20]");
+}
+
+#[test]
+fn let_vector_bindings_can_shadow_earlier_ones() {
+    let v = eval_program("(let [x 1 x (+ x 1)] x)").unwrap();
+    assert_eq!(v, "2");
+}
+
+#[test]
+fn let_rejects_odd_vector_bindings() {
+    let err = eval_program("(let [x 1 y] x)").unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg.contains("even number")
+    ));
+}
+
+#[test]
+fn loop_accepts_vector_and_map_bindings() {
+    let v = eval_program("(loop [n 5 acc 1] (if (< n 2) acc (recur (- n 1) (* acc n))))").unwrap();
+    assert_eq!(v, "120");
+
+    // Map bindings are rebound by `recur` in the order they are written.
+    let v = eval_program("(loop {n 5 acc 1} (if (< n 2) acc (recur (- n 1) (* acc n))))").unwrap();
+    assert_eq!(v, "120");
+
+    let v = eval_program("(def x 5) (loop {z 1 y (+ x 1)} (+ z y))").unwrap();
+    assert_eq!(v, "7");
+}
+
+#[test]
+fn let_and_loop_reject_odd_bindings_identically() {
+    for (src, form) in [("(let [x 1 y] x)", "let"), ("(loop [x 1 y] x)", "loop")] {
+        let err = eval_program(src).unwrap_err();
+        let crate::bezerro::error::EvalError::Custom(msg) = err else {
+            panic!("unexpected error for {src}: {err:?}");
+        };
+        assert_eq!(
+            msg,
+            format!("{form} bindings must have even number of forms")
+        );
+    }
+
+    let err = eval_program("(loop {x 1 y x} y)").unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg.starts_with("loop: map bindings are parallel")
+    ));
+}
+
+#[test]
+fn let_map_bindings_are_evaluated_in_outer_scope() {
+    let v = eval_program("(def x 5) (let {x (+ x 1) y 2} (+ x y))").unwrap();
+    assert_eq!(v, "8");
+}
+
+#[test]
+fn let_map_bindings_reject_references_to_siblings() {
+    let err = eval_program("(let {x 1 y (+ x 1)} y)").unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg.contains("map bindings are parallel")
+    ));
+
+    // Quoted mentions are data, not references.
+    let v = eval_program("(let {x 1 y (quote x)} y)").unwrap();
+    assert_eq!(v, "x");
+
+    // So are names rebound by a nested fn, let or loop.
+    let v = eval_program("(let {x 1 f (fn [x] (* x 2))} (f x))").unwrap();
+    assert_eq!(v, "2");
+    let v = eval_program("(let {x 1 y (let [x 20] (+ x 1))} (+ x y))").unwrap();
+    assert_eq!(v, "22");
+    let err = eval_program("(let {x 1 f (fn [y] (+ x y))} (f 1))").unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg.contains("map bindings are parallel")
+    ));
+}
+
 #[test]
 fn recur_allows_deep_tail_recursion_without_stack_overflow() {
     let v = eval_program(
@@ -132,6 +356,898 @@ fn non_tail_recursion_is_stopped_before_host_stack_overflow() {
     ));
 }
 
+#[test]
+fn multimethod_dispatches_on_keyword() {
+    let v = eval_program(
+        r#"
+        (defmulti area :shape)
+        (defmethod area :circle [s] "circle")
+        (defmethod area :square [s] "square")
+        (defmethod area :default [s] "unknown")
+        [(area {:shape :circle}) (area {:shape :square}) (area {:shape :triangle})]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, r#"["circle" "square" "unknown"]"#);
+}
+
+#[test]
+fn multimethod_keyword_dispatch_rejects_non_maps() {
+    let err = eval_program(
+        r#"
+        (defmulti area :shape)
+        (defmethod area :default [s] "unknown")
+        (area 5)
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::TypeError {
+            expected: "map",
+            got: "int"
+        }
+    ));
+    let v = eval_program(
+        r#"
+        (defmulti area :shape)
+        (defmethod area :default [s] "unknown")
+        (area nil)
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, r#""unknown""#);
+}
+
+#[test]
+fn multimethod_dispatches_through_function() {
+    let v = eval_program(
+        r#"
+        (defmulti classify (fn [n] (if (< n 0) :neg :pos)))
+        (defmethod classify :neg [n] (- n))
+        (defmethod classify :pos [n] n)
+        [(classify -3) (classify 4)]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, "[3 4]");
+}
+
+#[test]
+fn multimethod_without_matching_method_errors() {
+    let err = eval_program(
+        r#"
+        (defmulti area :shape)
+        (defmethod area :circle [s] 1)
+        (area {:shape :square})
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg.contains("no method")
+    ));
+
+    let err = eval_program("(defmethod nope :a [x] x)").unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg.contains("not a multimethod")
+    ));
+}
+
+#[test]
+fn closures_capture_every_free_local() {
+    let v = eval_program(
+        r#"
+        (def g 100)
+        (defn make [a]
+          (let [b (+ a 1) unused [1 2 3]]
+            (fn [x] (if x (+ a b g x) (quote unused)))))
+        ((make 1) 10)
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, "113");
+}
+
+#[test]
+fn closures_do_not_retain_unreferenced_locals() {
+    let root = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut root.borrow_mut());
+
+    let scope = Rc::new(RefCell::new(Env::with_parent(root.clone())));
+    scope.borrow_mut().define(
+        "big".to_string(),
+        Value::Vector((0..10_000).map(Value::Int).collect()),
+    );
+    scope
+        .borrow_mut()
+        .define("small".to_string(), Value::Int(2));
+    let f = eval_snippet(&scope, "(fn [x] (* x small))").unwrap();
+
+    let weak_scope = Rc::downgrade(&scope);
+    drop(scope);
+    assert!(weak_scope.upgrade().is_none());
+
+    let Value::Lambda { env: captured, .. } = &f else {
+        panic!("expected lambda, got {f}");
+    };
+    assert!(captured.borrow().get("big").is_none());
+    assert_eq!(apply(&f, &[Value::Int(21)], &root).unwrap(), Value::Int(42));
+}
+
+#[test]
+fn closures_created_in_a_loop_keep_their_iteration_values() {
+    let v = eval_program(
+        r#"
+        (loop [i 0 acc []]
+          (if (< i 3)
+            (recur (+ i 1) (prepend (fn [] i) acc))
+            (map (fn [f] (f)) acc)))
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, "[0 1 2]");
+}
+
+#[test]
+fn closures_can_refer_to_let_bindings_not_bound_yet() {
+    let v = eval_program("(let [n 5 f (fn [k] (if (< k 1) 0 (+ k (f (- k 1)))))] (f n))").unwrap();
+    assert_eq!(v, "15");
+
+    let v = eval_program(
+        r#"
+        (defn run [a]
+          (let [g (fn [] (h)) h (fn [] a)]
+            (g)))
+        (run 7)
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, "7");
+}
+
+#[test]
+fn try_catch_binds_the_error_message() {
+    let v = eval_program(r#"(try (+ 1 "a") (catch e e))"#).unwrap();
+    assert_eq!(v, r#""type error: expected number, got string""#);
+
+    let v = eval_program("(try 1 2 (catch e 0))").unwrap();
+    assert_eq!(v, "2");
+}
+
+#[test]
+fn recur_cannot_cross_try() {
+    let err = eval_program(
+        r#"
+        (defn f [n]
+          (try
+            (if (< n 1) 0 (recur (- n 1)))
+            (catch e -1)))
+        (f 3)
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg == "recur must be in tail position"
+    ));
+
+    let err = eval_program("(loop [n 1] (try (+ n \"x\") (catch e (recur 0))))").unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg == "recur must be in tail position"
+    ));
+}
+
+#[test]
+fn recur_around_try_still_works() {
+    let v = eval_program(
+        r#"
+        (defn f [n acc]
+          (if (< n 1)
+            acc
+            (recur (- n 1) (+ acc (try (mod 10 (- n 2)) (catch e 100))))))
+        (f 3 0)
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, "100");
+}
+
+#[test]
+fn eq_distinguishes_list_from_vector() {
+    assert_eq!(eval_program("(== (quote (1 2)) [1 2])").unwrap(), "false");
+    assert_eq!(eval_program("(!= (quote (1 2)) [1 2])").unwrap(), "true");
+    assert_eq!(eval_program("(== [1 2] [1 2])").unwrap(), "true");
+}
+
+#[test]
+fn seq_equal_compares_across_list_and_vector() {
+    assert_eq!(
+        eval_program("(seq-equal? (quote (1 2)) [1 2])").unwrap(),
+        "true"
+    );
+    assert_eq!(
+        eval_program("(seq-equal? [(quote (1 [2])) 3] (quote ([1 (2)] 3)))").unwrap(),
+        "true"
+    );
+    assert_eq!(
+        eval_program("(seq-equal? (quote (1 2)) [1 3])").unwrap(),
+        "false"
+    );
+    assert_eq!(
+        eval_program("(seq-equal? (quote (1 2)) [1 2 3])").unwrap(),
+        "false"
+    );
+    assert_eq!(eval_program("(seq-equal? 1 1.0)").unwrap(), "true");
+}
+
+#[test]
+fn trace_prints_calls_and_returns_by_depth() {
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    let out = env.borrow().capture_output();
+
+    let v = eval_snippet(
+        &env,
+        r#"
+        (defn fac [n] (if (<= n 1) 1 (* n (fac (- n 1)))))
+        (trace fac)
+        (fac 3)
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, Value::Int(6));
+    assert_eq!(
+        out.borrow().as_str(),
+        "(fac 3)\n  (fac 2)\n    (fac 1)\n    => 1\n  => 2\n=> 6\n"
+    );
+
+    out.borrow_mut().clear();
+    let v = eval_snippet(&env, "(untrace fac)\n(println (fac 4))").unwrap();
+    assert_eq!(v, Value::Nil);
+    assert_eq!(out.borrow().as_str(), "24\n");
+}
+
+#[test]
+fn trace_rejects_non_functions() {
+    let err = eval_program("(def x 1)\n(trace x)").unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::TypeError {
+            expected: "function",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn into_string_pours_chars_and_values() {
+    assert_eq!(eval_program(r#"(into "" [\a \b \c])"#).unwrap(), "\"abc\"");
+    assert_eq!(
+        eval_program(r#"(into "" (map (fn [c] (char+ c 1)) [\a \b \c]))"#).unwrap(),
+        "\"bcd\""
+    );
+    assert_eq!(
+        eval_program(r#"(into "n=" [1 "/" 2.5 :k])"#).unwrap(),
+        "\"n=1/2.5:k\""
+    );
+}
+
+#[test]
+fn apply_str_joins_collection() {
+    assert_eq!(
+        eval_program(r#"(apply str (filter (fn [c] (!= c \-)) [\a \- \b]))"#).unwrap(),
+        "\"ab\""
+    );
+    assert_eq!(eval_program("(into [1] (quote (2 3)))").unwrap(), "[1 2 3]");
+}
+
+#[test]
+fn eval_file_defines_into_env_and_surfaces_errors() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("helpers.vaca"),
+        "(defn twice [x] (* 2 x))\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("init.vaca"),
+        "(use helpers)\n(def answer (twice 21))\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("broken.vaca"), "(def ok 1)\n(+ ok \"a\")\n").unwrap();
+
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+
+    eval_file(&dir.path().join("init.vaca"), &env).unwrap();
+    assert_eq!(eval_snippet(&env, "answer").unwrap(), Value::Int(42));
+    assert_eq!(env.borrow().source_dir(), None);
+
+    let err = eval_file(&dir.path().join("broken.vaca"), &env).unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::TypeError { .. }
+    ));
+    // Forms before the failing one still ran.
+    assert_eq!(eval_snippet(&env, "ok").unwrap(), Value::Int(1));
+
+    let err = eval_file(&dir.path().join("missing.vaca"), &env).unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::Custom(msg) if msg.starts_with("failed to read")
+    ));
+}
+
+#[test]
+fn every_builtin_has_a_doc_entry() {
+    let mut env = Env::new();
+    register_builtins(&mut env);
+    let mut undocumented: Vec<_> = env
+        .local_names()
+        .filter(|name| crate::bezerro::builtin_doc(name).is_none())
+        .collect();
+    undocumented.sort();
+    assert!(env.contains_local("pi"));
+    assert_eq!(undocumented, Vec::<&str>::new());
+}
+
+#[test]
+fn help_text_describes_builtins_and_documented_defns() {
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    eval_snippet(
+        &env,
+        r#"
+        (defn area "Area of a square with the given side." [side] (* side side))
+        (defn undocumented [a b] a)
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        crate::bezerro::help_text("+", &env).unwrap(),
+        "(+ a b)\n  sums `a` and `b`"
+    );
+    assert_eq!(
+        crate::bezerro::help_text("area", &env).unwrap(),
+        "(area side)\n  Area of a square with the given side."
+    );
+    assert_eq!(
+        crate::bezerro::help_text("undocumented", &env).unwrap(),
+        "(undocumented a b)"
+    );
+    assert_eq!(crate::bezerro::help_text("nope", &env), None);
+
+    let out = env.borrow().capture_output();
+    eval_snippet(&env, "(help area)").unwrap();
+    assert_eq!(
+        out.borrow().as_str(),
+        "(area side)\n  Area of a square with the given side.\n"
+    );
+    assert_eq!(eval_snippet(&env, "(area 3)").unwrap(), Value::Int(9));
+}
+
+#[test]
+fn map_literals_keep_written_key_order() {
+    let src = "{:zeta 1 :alpha 2 :mid [3 {:y 4 :b 5}] \"k\" 6}";
+    assert_eq!(
+        eval_program(src).unwrap(),
+        "{:zeta 1 :alpha 2 :mid [3 {:y 4 :b 5}] \"k\" 6}"
+    );
+
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("config.vaca"),
+        format!("(def settings {src})\n(defn wrap [x] {{:value x :settings settings}})\n"),
+    )
+    .unwrap();
+    let imported = eval_in_dir(dir.path(), "(use config)\n(wrap 0)").unwrap();
+    let direct = eval_program(&format!(
+        "(def settings {src})\n(defn wrap [x] {{:value x :settings settings}})\n(wrap 0)"
+    ))
+    .unwrap();
+    assert_eq!(imported, direct);
+    assert!(imported.starts_with("{:value 0 :settings {:zeta 1 :alpha 2"));
+}
+
+#[test]
+fn has_key_and_has_val_check_keys_and_values_separately() {
+    assert_eq!(eval_program("(has-key? {:a 1} :a)").unwrap(), "true");
+    assert_eq!(eval_program("(has-val? {:a 1} 1)").unwrap(), "true");
+    assert_eq!(eval_program("(has-key? {:a 1} 1)").unwrap(), "false");
+    assert_eq!(eval_program("(has-val? {:a 1} :a)").unwrap(), "false");
+    assert!(matches!(
+        eval_program("(has-key? [1] 0)").unwrap_err(),
+        crate::bezerro::error::EvalError::TypeError {
+            expected: "map",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn table_aligns_columns() {
+    let v = eval_program(r#"(table [["id" "name" "score"] [7 "alice" 9.5]])"#).unwrap();
+    assert_eq!(v, "\"id  name   score\\n7   alice  9.5\"");
+
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    let v = eval_snippet(
+        &env,
+        r#"(table [[:a 1 "x"] [:bbb] ["c" 22]] :header ["key" "n"])"#,
+    )
+    .unwrap();
+    let Value::String(s) = v else {
+        panic!("expected string, got {v}");
+    };
+    assert_eq!(s, "key   n\n----  --  -\n:a    1   x\n:bbb\nc     22");
+}
+
+#[test]
+fn pad_right_pads_to_width() {
+    assert_eq!(eval_program(r#"(pad-right "ab" 4)"#).unwrap(), "\"ab  \"");
+    assert_eq!(
+        eval_program(r#"(pad-right "abcdef" 4)"#).unwrap(),
+        "\"abcdef\""
+    );
+    assert_eq!(eval_program(r#"(pad-right \é 2)"#).unwrap(), "\"é \"");
+    assert_eq!(
+        eval_program(r#"(pad-right "a" -3)"#)
+            .unwrap_err()
+            .to_string(),
+        "pad-right: width -3 is negative"
+    );
+}
+
+#[test]
+fn hash_is_equal_for_equal_values() {
+    for (a, b) in [
+        ("{:a 1 :b [2 3]}", "{:b [2 3] :a 1}"),
+        ("%{1 2 3}", "%{3 2 1}"),
+        ("1", "1.0"),
+        ("0.0", "-0.0"),
+        ("\"text\"", "\"text\""),
+    ] {
+        let src = format!("(== (hash {a}) (hash {b}))");
+        assert_eq!(eval_program(&src).unwrap(), "true", "{src}");
+    }
+
+    // Ints past 2^53 don't survive a round trip through float, so they must not be treated as one.
+    for (a, b) in [
+        ("9007199254740993", "9007199254740992"),
+        ("9007199254740993", "9007199254740992.0"),
+    ] {
+        let src = format!("[(== {a} {b}) (== (hash {a}) (hash {b}))]");
+        assert_eq!(eval_program(&src).unwrap(), "[false false]", "{src}");
+    }
+    assert_eq!(
+        eval_program("(== 9007199254740992 9007199254740992.0)").unwrap(),
+        "true"
+    );
+
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    let hashes = eval_snippet(
+        &env,
+        "(map hash [1 2 \"1\" :a [1] (quote (1)) {:a 1} {:a 2}])",
+    )
+    .unwrap();
+    let Value::Vector(hashes) = hashes else {
+        panic!("expected vector, got {hashes}");
+    };
+    #[allow(clippy::mutable_key_type)]
+    let distinct: std::collections::HashSet<_> = hashes.iter().collect();
+    assert_eq!(distinct.len(), hashes.len());
+    assert_eq!(
+        eval_snippet(&env, "(hash [1 2])").unwrap(),
+        eval_snippet(&env, "(hash [1 2])").unwrap()
+    );
+    // Pinned: hashes must not change between builds or platforms.
+    assert_eq!(
+        eval_snippet(&env, "(hash 1)").unwrap(),
+        Value::Int(4606856002140762924)
+    );
+}
+
+#[test]
+fn doto_runs_side_effects_and_returns_the_value() {
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    let out = env.borrow().capture_output();
+
+    let v = eval_snippet(
+        &env,
+        r#"(doto {:name "vaca" :tags [(quote a)]} (println " <- config") println)"#,
+    )
+    .unwrap();
+    assert_eq!(v.to_string(), "{:name \"vaca\" :tags [a]}");
+    assert_eq!(
+        out.borrow().as_str(),
+        "{:name \"vaca\" :tags [a]} <- config\n{:name \"vaca\" :tags [a]}\n"
+    );
+}
+
+#[test]
+fn find_on_maps_returns_the_entry() {
+    assert_eq!(eval_program("(find {:a 1 :b nil} :a)").unwrap(), "[:a 1]");
+    assert_eq!(eval_program("(find {:a 1 :b nil} :b)").unwrap(), "[:b nil]");
+    assert_eq!(eval_program("(find {:a 1 :b nil} :c)").unwrap(), "nil");
+    assert_eq!(
+        eval_program("(find (fn [x] (> x 1)) [1 2 3])").unwrap(),
+        "2"
+    );
+}
+
+#[test]
+fn find_index_returns_first_matching_index() {
+    assert_eq!(
+        eval_program("(find-index (fn [x] (> x 1)) [1 2 3])").unwrap(),
+        "1"
+    );
+    assert_eq!(
+        eval_program("(find-index (fn [x] (> x 5)) [1 2 3])").unwrap(),
+        "nil"
+    );
+}
+
+#[test]
+fn when_and_unless_run_body_on_opposite_tests() {
+    assert_eq!(eval_program("(unless false 1 2)").unwrap(), "2");
+    assert_eq!(eval_program("(unless nil 1)").unwrap(), "1");
+    assert_eq!(eval_program("(unless true (assert false))").unwrap(), "nil");
+    assert_eq!(eval_program("(when true 1 2)").unwrap(), "2");
+    assert_eq!(eval_program("(when false (assert false))").unwrap(), "nil");
+    assert_eq!(
+        eval_program("(loop [i 0] (if (< i 3) (recur (+ i 1)) (unless false i)))").unwrap(),
+        "3"
+    );
+    assert_eq!(
+        eval_program("(loop [i 0] (unless (>= i 3) (recur (+ i 1))))").unwrap(),
+        "nil"
+    );
+}
+
+#[test]
+fn subvec_copies_a_range() {
+    assert_eq!(eval_program("(subvec [1 2 3 4] 1 3)").unwrap(), "[2 3]");
+    assert_eq!(eval_program("(subvec [1 2 3 4] 1)").unwrap(), "[2 3 4]");
+    assert_eq!(eval_program("(subvec [1 2 3 4] 4)").unwrap(), "[]");
+    assert!(matches!(
+        eval_program("(subvec [1 2 3 4] 1 5)").unwrap_err(),
+        crate::bezerro::error::EvalError::IndexOutOfBounds { index: 5, len: 4 }
+    ));
+    assert!(eval_program("(subvec [1 2 3 4] 3 1)").is_err());
+    assert_eq!(
+        eval_program("(subvec [1 2 3] -1)").unwrap_err().to_string(),
+        "subvec: index -1 is negative"
+    );
+    assert_eq!(
+        eval_program("(subvec [1 2 3] 0 -2)")
+            .unwrap_err()
+            .to_string(),
+        "subvec: index -2 is negative"
+    );
+}
+
+#[test]
+fn iterate_n_builds_bounded_sequences() {
+    assert_eq!(
+        eval_program("(defn inc [x] (+ x 1)) (iterate-n inc 0 5)").unwrap(),
+        "[0 1 2 3 4]"
+    );
+    assert_eq!(eval_program("(iterate-n assert 0 0)").unwrap(), "[]");
+    // `f` is only called for elements that are kept.
+    assert_eq!(
+        eval_program("(iterate-n assert false 1)").unwrap(),
+        "[false]"
+    );
+}
+
+#[test]
+fn lazy_seqs_realize_finite_prefixes_of_infinite_sequences() {
+    let src = "(defn inc [x] (+ x 1))";
+    assert_eq!(
+        eval_program(&format!("{src} (take 5 (iterate inc 0))")).unwrap(),
+        "[0 1 2 3 4]"
+    );
+    assert_eq!(
+        eval_program(&format!(
+            "{src} (take 3 (filter (fn [x] (== (mod x 2) 0)) (map (fn [x] (* x x)) (iterate inc 0))))"
+        ))
+        .unwrap(),
+        "[0 4 16]"
+    );
+    assert_eq!(
+        eval_program(&format!("{src} (take 2 (drop 3 (iterate inc 0)))")).unwrap(),
+        "[3 4]"
+    );
+    assert_eq!(eval_program("(drop 1 [1 2 3])").unwrap(), "[2 3]");
+}
+
+#[test]
+fn lazy_seqs_are_realized_once() {
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    let out = env.borrow().capture_output();
+
+    let v = eval_snippet(
+        &env,
+        r#"
+        (def nums (iterate (fn [x] (print "*") (+ x 1)) 0))
+        (take 3 nums)
+        (take 2 nums)
+        (take 3 nums)
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v.to_string(), "[0 1 2]");
+    assert_eq!(out.borrow().as_str(), "**");
+    assert_eq!(
+        eval_snippet(&env, "nums").unwrap().to_string(),
+        "(0 1 2 ...)"
+    );
+}
+
+#[test]
+fn lazy_seqs_retry_an_element_that_failed() {
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+    eval_snippet(
+        &env,
+        r#"
+        (defn inc [x] (+ x 1))
+        (def mapped (map (fn [x] (if (== x 0) (assert false) x)) (iterate inc 0)))
+        (def kept (filter (fn [x] (if (== x 0) (assert false) true)) (iterate inc 0)))
+        "#,
+    )
+    .unwrap();
+
+    for name in ["mapped", "kept"] {
+        for n in [1, 2] {
+            let src = format!("(take {n} {name})");
+            assert!(eval_snippet(&env, &src).is_err(), "{src}");
+        }
+    }
+}
+
+#[test]
+fn reduce_lines_streams_a_file_relative_to_source_dir() {
+    let dir = tempdir().unwrap();
+    // Well past BufReader's 8 KiB buffer, with a CRLF line thrown in.
+    let mut text: String = (0..5000).map(|i| format!("{i}\n")).collect();
+    text.push_str("5000\r\n");
+    fs::write(dir.path().join("nums.txt"), text).unwrap();
+
+    let v = eval_in_dir(
+        dir.path(),
+        r#"(reduce-lines "nums.txt" (fn [acc line] (+ acc (parse-int line))) 0)"#,
+    )
+    .unwrap();
+    assert_eq!(v, (0..=5000).sum::<i64>().to_string());
+
+    let err = eval_in_dir(dir.path(), r#"(reduce-lines "missing.txt" + 0)"#).unwrap_err();
+    assert!(err.to_string().starts_with("reduce-lines: "));
+}
+
+#[test]
+fn use_reports_structured_parse_errors_with_module_path() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("bad.vaca"), "(def a 1)\n(def b [1 2)\n").unwrap();
+
+    let err = eval_in_dir(dir.path(), "(use bad)").unwrap_err();
+    let crate::bezerro::error::EvalError::ParseError {
+        path: Some(path),
+        error,
+    } = &err
+    else {
+        panic!("expected a parse error with a path, got {err:?}");
+    };
+    assert_eq!(path.file_name().unwrap(), "bad.vaca");
+    assert_eq!(error.line, 2);
+    assert_eq!(&"(def a 1)\n(def b [1 2)\n"[error.span.start..], ")\n");
+    assert!(err.to_string().starts_with("parse error in `"));
+}
+
+#[test]
+fn transients_build_collections_in_place() {
+    let src = r#"
+        (defn inc [x] (+ x 1))
+        (def built
+          (loop [t (transient []) i 0]
+            (if (< i 10000) (recur (conj! t i) (+ i 1)) (persistent! t))))
+        (== built (iterate-n inc 0 10000))
+    "#;
+    assert_eq!(eval_program(src).unwrap(), "true");
+
+    let src = r#"
+        (def base {:a 1})
+        (def t (transient base))
+        (assoc! t :b 2)
+        (assoc! t :a 3)
+        [base (persistent! t)]
+    "#;
+    assert_eq!(eval_program(src).unwrap(), "[{:a 1} {:a 3 :b 2}]");
+}
+
+#[test]
+fn transients_cannot_be_used_after_persistent() {
+    let src = "(def t (transient [])) (persistent! t) (conj! t 1)";
+    let err = eval_program(src).unwrap_err();
+    assert_eq!(err.to_string(), "conj!: transient used after persistent!");
+    assert!(eval_program("(def t (transient [])) (persistent! t) (persistent! t)").is_err());
+    assert!(eval_program("(conj! (transient {}) 1)").is_err());
+}
+
+#[test]
+fn predicate_combinators_and_remove() {
+    let defs = "(defn pos? [x] (> x 0)) (defn even? [x] (== (mod x 2) 0))";
+    let run = |src: &str| eval_program(&format!("{defs} {src}")).unwrap();
+
+    assert_eq!(
+        run("(filter (every-pred pos? even?) [-2 -1 0 1 2 3 4])"),
+        "[2 4]"
+    );
+    assert_eq!(
+        run("(filter (some-fn pos? even?) [-2 -1 0 1 2])"),
+        "[-2 0 1 2]"
+    );
+    assert_eq!(run("(remove (every-pred pos? even?) [-2 1 2])"), "[-2 1]");
+    assert_eq!(run("((every-pred pos? even?) 2 4)"), "true");
+    assert_eq!(run("((every-pred pos? even?) 2 3)"), "false");
+    assert_eq!(run("((some-fn pos?) -1 -2)"), "nil");
+    assert_eq!(run("((every-pred) 1)"), "true");
+}
+
+#[test]
+fn sort_uses_natural_order_or_a_comparator() {
+    assert_eq!(eval_program("(sort [3 1.5 2 -1])").unwrap(), "[-1 1.5 2 3]");
+    assert_eq!(
+        eval_program(r#"(sort ["b" "a" "c"])"#).unwrap(),
+        r#"["a" "b" "c"]"#
+    );
+    assert_eq!(
+        eval_program("(sort (fn [a b] (- b a)) [3 1 2])").unwrap(),
+        "[3 2 1]"
+    );
+    assert_eq!(eval_program("(sort > [3 1 2])").unwrap(), "[3 2 1]");
+    assert!(eval_program("(sort [1 :a])").is_err());
+}
+
+#[test]
+fn sort_is_stable_on_equal_keys() {
+    let src = "(sort-by (fn [p] (nth 0 p)) [[2 :a] [1 :b] [2 :c] [1 :d] [2 :e]])";
+    assert_eq!(
+        eval_program(src).unwrap(),
+        "[[1 :b] [1 :d] [2 :a] [2 :c] [2 :e]]"
+    );
+    let src = "(sort (fn [a b] (- (nth 0 b) (nth 0 a))) [[1 :a] [2 :b] [1 :c] [2 :d]])";
+    assert_eq!(eval_program(src).unwrap(), "[[2 :b] [2 :d] [1 :a] [1 :c]]");
+}
+
+#[test]
+fn keywords_in_call_position_read_record_fields() {
+    let defs = "(defn Point [x y] {:x x :y y})";
+    let run = |src: &str| eval_program(&format!("{defs} {src}"));
+
+    assert_eq!(run("(:x (Point 1 2))").unwrap(), "1");
+    assert_eq!(run("(:z (Point 1 2))").unwrap(), "nil");
+    assert_eq!(run("(:z (Point 1 2) 0)").unwrap(), "0");
+    assert_eq!(run("(:x nil)").unwrap(), "nil");
+    assert_eq!(run("(map :y [(Point 1 2) (Point 3 4)])").unwrap(), "[2 4]");
+    assert_eq!(run("(get (Point 1 2) :y)").unwrap(), "2");
+    assert_eq!(run("(get {1 nil} 1 :absent)").unwrap(), "nil");
+    assert_eq!(run("(get {} 1 :absent)").unwrap(), ":absent");
+    assert!(matches!(
+        run("(:x [1 2])").unwrap_err(),
+        crate::bezerro::error::EvalError::TypeError {
+            expected: "map",
+            ..
+        }
+    ));
+    assert_eq!(
+        run("(:x (Point 1 2) 0 1)").unwrap_err().to_string(),
+        ":x expects: (:x m) or (:x m default)"
+    );
+}
+
+#[test]
+fn with_redefs_rebinds_globals_for_its_body_only() {
+    let defs = r#"
+        (defn greet [] "hi")
+        (defn call-greet [] (greet))
+    "#;
+    let run = |src: &str| eval_program(&format!("{defs} {src}"));
+
+    assert_eq!(
+        run(r#"(with-redefs [greet (fn [] "stub")] (call-greet))"#).unwrap(),
+        r#""stub""#
+    );
+    assert_eq!(
+        run(r#"(with-redefs [greet (fn [] "stub")] 1) (call-greet)"#).unwrap(),
+        r#""hi""#
+    );
+    assert_eq!(
+        run(r#"(try (with-redefs [greet (fn [] "stub")] (assert false)) (catch e nil)) (call-greet)"#)
+            .unwrap(),
+        r#""hi""#
+    );
+    assert!(matches!(
+        run("(with-redefs [missing 1] nil)").unwrap_err(),
+        crate::bezerro::error::EvalError::UndefinedSymbol(_)
+    ));
+}
+
+#[test]
+fn with_redefs_rebinds_functions_imported_by_use() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("lib.vaca"),
+        "(defn greet [] \"hi\")\n(defn call-greet [] (greet))\n",
+    )
+    .unwrap();
+
+    let v = eval_in_dir(
+        dir.path(),
+        r#"
+        (use lib)
+        [(with-redefs [greet (fn [] "stub")] (call-greet)) (call-greet) (greet)]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, r#"["stub" "hi" "hi"]"#);
+
+    let v = eval_in_dir(
+        dir.path(),
+        r#"
+        (use lib [greet :as hello call-greet])
+        (with-redefs [hello (fn [] "stub")] (call-greet))
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, r#""stub""#);
+}
+
+#[test]
+fn numeric_coercions() {
+    let env = Rc::new(RefCell::new(Env::new()));
+    register_builtins(&mut env.borrow_mut());
+
+    assert_eq!(eval_snippet(&env, "(int 3.9)").unwrap(), Value::Int(3));
+    assert_eq!(eval_snippet(&env, "(int -3.9)").unwrap(), Value::Int(-3));
+    assert_eq!(eval_snippet(&env, "(float 3)").unwrap(), Value::Float(3.0));
+    assert_eq!(
+        eval_snippet(&env, r#"(num "4.5")"#).unwrap(),
+        Value::Float(4.5)
+    );
+    assert_eq!(
+        eval_snippet(&env, r#"(num " -4 ")"#).unwrap(),
+        Value::Int(-4)
+    );
+
+    assert!(eval_snippet(&env, r#"(int (parse-float "NaN"))"#).is_err());
+    assert!(eval_snippet(&env, r#"(int (parse-float "inf"))"#).is_err());
+    assert!(eval_snippet(&env, "(int 1e19)").is_err());
+    assert!(eval_snippet(&env, r#"(num "4.5 6")"#).is_err());
+    assert!(eval_snippet(&env, r#"(num "four")"#).is_err());
+    for text in ["## 7 8", "9 ; c", "#a 9"] {
+        let src = format!("(num {text:?})");
+        assert!(eval_snippet(&env, &src).is_err(), "{src}");
+    }
+
+    for (text, message) in [
+        ("1N", "num: precision suffixes are not supported"),
+        ("2.5M", "num: precision suffixes are not supported"),
+        ("99999999999999999999", "num: out of range"),
+        ("1e400", "num: out of range"),
+    ] {
+        let err = eval_snippet(&env, &format!("(num {text:?})")).unwrap_err();
+        assert!(err.to_string().starts_with(message), "{text}: {err}");
+    }
+}
+
 #[test]
 fn use_imports_all_exports() {
     let dir = tempdir().unwrap();
@@ -227,6 +1343,46 @@ fn use_aliasing_works_and_original_name_is_not_required() {
     ));
 }
 
+#[test]
+fn use_resolves_directory_modules_through_their_index() {
+    let dir = tempdir().unwrap();
+    let shapes = dir.path().join("shapes");
+    fs::create_dir(&shapes).unwrap();
+    fs::write(
+        shapes.join("mod.vaca"),
+        "(use square)\n(def unit (area 1))\n",
+    )
+    .unwrap();
+    // Relative uses inside the index resolve against the module's own directory.
+    fs::write(shapes.join("square.vaca"), "(defn area [s] (* s s))\n").unwrap();
+
+    let v = eval_in_dir(dir.path(), "(use shapes [unit])\nunit").unwrap();
+    assert_eq!(v, "1");
+}
+
+#[test]
+fn use_distinguishes_missing_module_from_directory_without_index() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("empty")).unwrap();
+
+    let err = eval_in_dir(dir.path(), "(use empty)").unwrap_err();
+    assert!(matches!(
+        &err,
+        crate::bezerro::error::EvalError::Use(UseError::DirectoryWithoutIndex { module, .. })
+            if module == "empty"
+    ));
+    assert!(err.to_string().contains("has no `mod.vaca`"));
+
+    let err = eval_in_dir(dir.path(), "(use nowhere)").unwrap_err();
+    let crate::bezerro::error::EvalError::Use(UseError::ModuleNotFound { module, tried }) = &err
+    else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(module, "nowhere");
+    assert!(tried[0].ends_with("nowhere.vaca"));
+    assert!(tried[1].ends_with("mod.vaca"));
+}
+
 #[test]
 fn use_errors_on_missing_export() {
     let dir = tempdir().unwrap();
@@ -245,6 +1401,45 @@ fn use_errors_on_missing_export() {
     ));
 }
 
+#[test]
+fn use_does_not_export_private_definitions() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("shapes.vaca"),
+        r#"
+        (def- unit 1)
+        (defn- square [x] (* x x))
+        (defn area [side] (square (+ side unit)))
+        "#,
+    )
+    .unwrap();
+
+    let v = eval_in_dir(
+        dir.path(),
+        r#"
+        (use shapes)
+        (area 2)
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, "9");
+
+    for src in ["(use shapes [square])", "(use shapes [unit])"] {
+        let err = eval_in_dir(dir.path(), src).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::bezerro::error::EvalError::Use(UseError::MissingExport { .. })
+        ));
+    }
+
+    // Private names are mangled, so they don't leak into the importer's scope either.
+    let err = eval_in_dir(dir.path(), "(use shapes)\n(square 2)").unwrap_err();
+    assert!(matches!(
+        err,
+        crate::bezerro::error::EvalError::UndefinedSymbol(_)
+    ));
+}
+
 #[test]
 fn use_macros_work_even_if_helpers_are_not_imported() {
     let dir = tempdir().unwrap();
@@ -318,6 +1513,55 @@ fn use_rewrite_is_binder_aware_in_macro_expansions() {
     assert_eq!(v, "1");
 }
 
+#[test]
+fn use_rewrites_vector_let_bindings_sequentially() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("v.vaca"),
+        r#"
+        (def base 10)
+        (defn f [n]
+          (let [a (+ base n) b (* a 2)] b))
+        "#,
+    )
+    .unwrap();
+
+    let v = eval_in_dir(
+        dir.path(),
+        r#"
+        (use v [f])
+        (f 1)
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, "22");
+}
+
+#[test]
+fn use_imports_multimethods_with_their_methods() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("shapes.vaca"),
+        r#"
+        (defn twice [n] (* 2 n))
+        (defmulti size (fn [s] (nth 0 s)))
+        (defmethod size :pair [s] (twice (nth 1 s)))
+        "#,
+    )
+    .unwrap();
+
+    let v = eval_in_dir(
+        dir.path(),
+        r#"
+        (use shapes [size])
+        (defmethod size :one [s] 1)
+        [(size [:pair 5]) (size [:one])]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(v, "[10 1]");
+}
+
 #[test]
 fn use_super_resolves_parent_directory() {
     let dir = tempdir().unwrap();
@@ -7,7 +7,8 @@ use std::rc::Rc;
 
 use crate::bezerro::env::{define_global, root_env, Env, ModuleInfo};
 use crate::bezerro::error::{EvalError, UseError};
-use crate::bezerro::value::Value;
+use crate::bezerro::ordered_map::OrderedMap;
+use crate::bezerro::value::{intern, Value};
 
 use super::core::{eval_value_impl, node_to_form, recur_tail_position_error, SPECIAL_FORM_HEADS};
 
@@ -46,7 +47,7 @@ pub(super) fn special_use(
     for (orig, visible) in requested {
         if !module_info.exports.contains(&orig) {
             return Err(EvalError::Use(UseError::MissingExport {
-                module: module_spec.clone(),
+                module: module_spec.to_string(),
                 symbol: orig,
             }));
         }
@@ -67,6 +68,10 @@ pub(super) fn special_use(
             })
         })?;
 
+        root.borrow()
+            .imports()
+            .borrow_mut()
+            .insert(visible.clone(), mangled.clone());
         define_global(&root, visible, value);
     }
 
@@ -100,14 +105,14 @@ fn parse_use_import_list(form: &Value) -> Result<Vec<(String, String)>, EvalErro
                     };
                     visible = alias.clone();
                     i += 3;
-                    out.push((orig.clone(), visible));
+                    out.push((orig.to_string(), visible.to_string()));
                     continue;
                 }
             }
         }
 
         i += 1;
-        out.push((orig.clone(), visible));
+        out.push((orig.to_string(), visible.to_string()));
     }
     Ok(out)
 }
@@ -117,14 +122,14 @@ fn resolve_module_path(module_spec: &str, root: &Rc<RefCell<Env>>) -> Result<Pat
         .borrow()
         .source_dir()
         .or_else(|| std::env::current_dir().ok())
-        .ok_or_else(|| EvalError::Use(UseError::FailedToDetermineBaseDir))?;
+        .ok_or(EvalError::Use(UseError::FailedToDetermineBaseDir))?;
 
     let parts: Vec<&str> = module_spec.split('.').filter(|p| !p.is_empty()).collect();
     if parts.is_empty() {
         return Err(EvalError::Use(UseError::EmptyModulePath));
     }
 
-    // Flat file mapping: `a.b.c` -> `<base>/a/b/c.vaca`
+    // `a.b.c` -> `<base>/a/b/c.vaca`, or `<base>/a/b/c/mod.vaca` for a directory module.
     let mut dir = base_dir;
     for seg in &parts[..parts.len() - 1] {
         if *seg == "super" {
@@ -142,8 +147,29 @@ fn resolve_module_path(module_spec: &str, root: &Rc<RefCell<Env>>) -> Result<Pat
     if file == "super" {
         return Err(EvalError::Use(UseError::LastSegmentCannotBeSuper));
     }
-    dir.push(format!("{file}.vaca"));
-    Ok(dir)
+
+    let file_path = dir.join(format!("{file}.vaca"));
+    if file_path.is_file() {
+        return Ok(file_path);
+    }
+    let module_dir = dir.join(file);
+    let index_path = module_dir.join("mod.vaca");
+    if index_path.is_file() {
+        return Ok(index_path);
+    }
+    if module_dir.is_dir() {
+        return Err(EvalError::Use(UseError::DirectoryWithoutIndex {
+            module: module_spec.to_string(),
+            dir: module_dir.display().to_string(),
+        }));
+    }
+    Err(EvalError::Use(UseError::ModuleNotFound {
+        module: module_spec.to_string(),
+        tried: vec![
+            file_path.display().to_string(),
+            index_path.display().to_string(),
+        ],
+    }))
 }
 
 fn ensure_module_loaded(
@@ -182,13 +208,18 @@ fn ensure_module_loaded(
             })
         })?;
 
-        let nodes = crate::parse(&src).map_err(|e| EvalError::ParseError(e.to_string()))?;
+        let nodes = crate::parse(&src).map_err(|error| EvalError::ParseError {
+            path: Some(module_path.clone()),
+            error,
+        })?;
         let forms: Vec<Value> = nodes.iter().map(node_to_form).collect();
 
         let exports = collect_module_exports(&forms)?;
+        let private = collect_private_definitions(&forms)?;
         let module_key = module_key_hash(&module_path);
         let mangle_map = exports
             .iter()
+            .chain(&private)
             .map(|orig| (orig.clone(), format!("__use__{module_key}__{orig}")))
             .collect::<HashMap<_, _>>();
 
@@ -236,7 +267,17 @@ fn module_key_hash(path: &Path) -> String {
     format!("{:x}", h.finish())
 }
 
+/// Names defined by the module's public definition forms. `def-`/`defn-` are skipped.
 fn collect_module_exports(forms: &[Value]) -> Result<HashSet<String>, EvalError> {
+    collect_definitions(forms, &["def", "defn", "defmacro", "defmulti"])
+}
+
+/// Names defined by `def-`/`defn-`. They are mangled like exports but never importable.
+fn collect_private_definitions(forms: &[Value]) -> Result<HashSet<String>, EvalError> {
+    collect_definitions(forms, &["def-", "defn-"])
+}
+
+fn collect_definitions(forms: &[Value], heads: &[&str]) -> Result<HashSet<String>, EvalError> {
     let mut out = HashSet::new();
     for form in forms {
         let Value::List(items) = form else { continue };
@@ -246,7 +287,7 @@ fn collect_module_exports(forms: &[Value]) -> Result<HashSet<String>, EvalError>
         let Value::Symbol(head) = &items[0] else {
             continue;
         };
-        if !matches!(head.as_str(), "def" | "defn" | "defmacro") {
+        if !heads.contains(&&**head) {
             continue;
         }
         let Value::Symbol(name) = &items[1] else {
@@ -254,7 +295,7 @@ fn collect_module_exports(forms: &[Value]) -> Result<HashSet<String>, EvalError>
                 head: head.to_string(),
             }));
         };
-        out.insert(name.clone());
+        out.insert(name.to_string());
     }
     Ok(out)
 }
@@ -271,11 +312,11 @@ fn rewrite_form_impl(
 ) -> Value {
     match form {
         Value::Symbol(name) => {
-            if shadowed.contains(name) {
+            if shadowed.contains(&**name) {
                 return form.clone();
             }
-            if let Some(mapped) = mangle.get(name) {
-                return Value::Symbol(mapped.clone());
+            if let Some(mapped) = mangle.get(&**name) {
+                return Value::Symbol(intern(mapped));
             }
             form.clone()
         }
@@ -287,7 +328,7 @@ fn rewrite_form_impl(
                 .collect(),
         ),
         Value::Map(entries) => {
-            let mut out: HashMap<Value, Value> = HashMap::with_capacity(entries.len());
+            let mut out = OrderedMap::with_capacity(entries.len());
             for (k, v) in entries.iter() {
                 out.insert(
                     rewrite_form_impl(k, mangle, shadowed, rewrite_in_quote),
@@ -297,6 +338,7 @@ fn rewrite_form_impl(
             Value::Map(Rc::new(out))
         }
         Value::Set(items) => {
+            #[allow(clippy::mutable_key_type)]
             let mut out = HashSet::with_capacity(items.len());
             for item in items.iter() {
                 out.insert(rewrite_form_impl(item, mangle, shadowed, rewrite_in_quote));
@@ -318,7 +360,7 @@ fn rewrite_list_impl(
     }
 
     let head_sym = match &items[0] {
-        Value::Symbol(s) => Some(s.as_str()),
+        Value::Symbol(s) => Some(&**s),
         _ => None,
     };
 
@@ -328,7 +370,7 @@ fn rewrite_list_impl(
     }
 
     match head_sym {
-        Some("def") => {
+        Some("def" | "def-") => {
             if items.len() != 3 {
                 return Value::List(items.to_vec());
             }
@@ -337,9 +379,8 @@ fn rewrite_list_impl(
                 _ => return Value::List(items.to_vec()),
             };
             let new_name = mangle
-                .get(name)
-                .cloned()
-                .map(Value::Symbol)
+                .get(&**name)
+                .map(|mapped| Value::Symbol(intern(mapped)))
                 .unwrap_or_else(|| items[1].clone());
             Value::List(vec![
                 items[0].clone(),
@@ -347,7 +388,7 @@ fn rewrite_list_impl(
                 rewrite_form_impl(&items[2], mangle, shadowed, rewrite_in_quote),
             ])
         }
-        Some("defn") => {
+        Some("defn" | "defn-") => {
             if items.len() < 4 {
                 return Value::List(items.to_vec());
             }
@@ -356,26 +397,31 @@ fn rewrite_list_impl(
                 _ => return Value::List(items.to_vec()),
             };
             let new_name = mangle
-                .get(name)
-                .cloned()
-                .map(Value::Symbol)
+                .get(&**name)
+                .map(|mapped| Value::Symbol(intern(mapped)))
                 .unwrap_or_else(|| items[1].clone());
 
-            let Value::Vector(params) = &items[2] else {
+            // Optional docstring before the params.
+            let params_at = if matches!(items[2], Value::String(_)) {
+                3
+            } else {
+                2
+            };
+            let Some(Value::Vector(params)) = items.get(params_at) else {
                 return Value::List(items.to_vec());
             };
             let mut new_shadowed = shadowed.clone();
             for p in params {
                 if let Value::Symbol(s) = p {
-                    new_shadowed.insert(s.clone());
+                    new_shadowed.insert(s.to_string());
                 }
             }
 
             let mut out = Vec::with_capacity(items.len());
-            out.push(items[0].clone()); // defn
+            out.push(items[0].clone()); // defn / defn-
             out.push(new_name);
-            out.push(items[2].clone()); // params untouched
-            for b in &items[3..] {
+            out.extend_from_slice(&items[2..=params_at]); // docstring and params untouched
+            for b in &items[params_at + 1..] {
                 out.push(rewrite_form_impl(
                     b,
                     mangle,
@@ -394,9 +440,8 @@ fn rewrite_list_impl(
                 _ => return Value::List(items.to_vec()),
             };
             let new_name = mangle
-                .get(name)
-                .cloned()
-                .map(Value::Symbol)
+                .get(&**name)
+                .map(|mapped| Value::Symbol(intern(mapped)))
                 .unwrap_or_else(|| items[1].clone());
 
             let Value::Vector(params) = &items[2] else {
@@ -405,7 +450,7 @@ fn rewrite_list_impl(
             let mut new_shadowed = shadowed.clone();
             for p in params {
                 if let Value::Symbol(s) = p {
-                    new_shadowed.insert(s.clone());
+                    new_shadowed.insert(s.to_string());
                 }
             }
 
@@ -418,38 +463,19 @@ fn rewrite_list_impl(
             }
             Value::List(out)
         }
-        Some("let") => {
+        Some("let" | "loop") => {
             if items.len() < 3 {
                 return Value::List(items.to_vec());
             }
-            let Value::Map(bindings) = &items[1] else {
-                return Value::List(items.to_vec());
-            };
-
-            // Map bindings are treated as a "parallel" binder:
-            // - binder symbols are not rewritten
-            // - all binding values are rewritten in the *outer* scope
-            // - the body is rewritten in the scope extended by all binder symbols
-            let mut scoped = shadowed.clone();
-            for k in bindings.keys() {
-                if let Value::Symbol(s) = k {
-                    scoped.insert(s.clone());
+            match &items[1] {
+                Value::Vector(bindings) => {
+                    rewrite_sequential_bindings(items, bindings, mangle, shadowed, rewrite_in_quote)
                 }
+                Value::Map(bindings) => {
+                    rewrite_parallel_bindings(items, bindings, mangle, shadowed, rewrite_in_quote)
+                }
+                _ => Value::List(items.to_vec()),
             }
-
-            let mut new_bindings: HashMap<Value, Value> = HashMap::with_capacity(bindings.len());
-            for (k, v) in bindings.iter() {
-                let new_v = rewrite_form_impl(v, mangle, shadowed, rewrite_in_quote);
-                new_bindings.insert(k.clone(), new_v);
-            }
-
-            let mut out = Vec::with_capacity(items.len());
-            out.push(items[0].clone());
-            out.push(Value::Map(Rc::new(new_bindings)));
-            for b in &items[2..] {
-                out.push(rewrite_form_impl(b, mangle, &scoped, rewrite_in_quote));
-            }
-            Value::List(out)
         }
         Some("fn") => {
             if items.len() < 3 {
@@ -461,7 +487,7 @@ fn rewrite_list_impl(
             let mut scoped = shadowed.clone();
             for p in params {
                 if let Value::Symbol(s) = p {
-                    scoped.insert(s.clone());
+                    scoped.insert(s.to_string());
                 }
             }
 
@@ -473,33 +499,36 @@ fn rewrite_list_impl(
             }
             Value::List(out)
         }
-        Some("loop") => {
-            if items.len() < 3 {
+        Some("defmethod") => {
+            if items.len() < 5 {
                 return Value::List(items.to_vec());
             }
-            let Value::Vector(bindings) = &items[1] else {
+            let Value::Vector(params) = &items[3] else {
                 return Value::List(items.to_vec());
             };
-            if bindings.len() % 2 != 0 {
-                return Value::List(items.to_vec());
-            }
-
-            let mut new_bindings = Vec::with_capacity(bindings.len());
             let mut scoped = shadowed.clone();
-            for pair in bindings.chunks(2) {
-                let name = &pair[0];
-                let value = &pair[1];
-                new_bindings.push(name.clone());
-                new_bindings.push(rewrite_form_impl(value, mangle, &scoped, rewrite_in_quote));
-                if let Value::Symbol(s) = name {
-                    scoped.insert(s.clone());
+            for p in params {
+                if let Value::Symbol(s) = p {
+                    scoped.insert(s.to_string());
                 }
             }
 
             let mut out = Vec::with_capacity(items.len());
             out.push(items[0].clone());
-            out.push(Value::Vector(new_bindings));
-            for b in &items[2..] {
+            out.push(rewrite_form_impl(
+                &items[1],
+                mangle,
+                shadowed,
+                rewrite_in_quote,
+            ));
+            out.push(rewrite_form_impl(
+                &items[2],
+                mangle,
+                shadowed,
+                rewrite_in_quote,
+            ));
+            out.push(items[3].clone()); // params untouched
+            for b in &items[4..] {
                 out.push(rewrite_form_impl(b, mangle, &scoped, rewrite_in_quote));
             }
             Value::List(out)
@@ -517,7 +546,7 @@ fn rewrite_list_impl(
             let mut out = Vec::with_capacity(items.len());
             // Head element: don't rewrite if it is a special form name.
             if let Value::Symbol(s) = &items[0] {
-                if SPECIAL_FORM_HEADS.contains(&s.as_str()) {
+                if SPECIAL_FORM_HEADS.contains(&&**s) {
                     out.push(items[0].clone());
                 } else {
                     out.push(rewrite_form_impl(
@@ -542,3 +571,74 @@ fn rewrite_list_impl(
         }
     }
 }
+
+/// Rewrites a `(let {...} body...)` / `(loop {...} body...)` form.
+///
+/// Map bindings are a "parallel" binder:
+/// - binder symbols are not rewritten
+/// - all binding values are rewritten in the *outer* scope
+/// - the body is rewritten in the scope extended by all binder symbols
+fn rewrite_parallel_bindings(
+    items: &[Value],
+    bindings: &OrderedMap,
+    mangle: &HashMap<String, String>,
+    shadowed: &HashSet<String>,
+    rewrite_in_quote: bool,
+) -> Value {
+    let mut scoped = shadowed.clone();
+    for k in bindings.keys() {
+        if let Value::Symbol(s) = k {
+            scoped.insert(s.to_string());
+        }
+    }
+
+    let mut new_bindings = OrderedMap::with_capacity(bindings.len());
+    for (k, v) in bindings.iter() {
+        let new_v = rewrite_form_impl(v, mangle, shadowed, rewrite_in_quote);
+        new_bindings.insert(k.clone(), new_v);
+    }
+
+    let mut out = Vec::with_capacity(items.len());
+    out.push(items[0].clone());
+    out.push(Value::Map(Rc::new(new_bindings)));
+    for b in &items[2..] {
+        out.push(rewrite_form_impl(b, mangle, &scoped, rewrite_in_quote));
+    }
+    Value::List(out)
+}
+
+/// Rewrites a `(let [...] body...)` / `(loop [...] body...)` form.
+///
+/// Vector bindings are sequential, so each binding value is rewritten in the scope extended by
+/// the binders that precede it.
+fn rewrite_sequential_bindings(
+    items: &[Value],
+    bindings: &[Value],
+    mangle: &HashMap<String, String>,
+    shadowed: &HashSet<String>,
+    rewrite_in_quote: bool,
+) -> Value {
+    if !bindings.len().is_multiple_of(2) {
+        return Value::List(items.to_vec());
+    }
+
+    let mut new_bindings = Vec::with_capacity(bindings.len());
+    let mut scoped = shadowed.clone();
+    for pair in bindings.chunks(2) {
+        let name = &pair[0];
+        let value = &pair[1];
+        new_bindings.push(name.clone());
+        new_bindings.push(rewrite_form_impl(value, mangle, &scoped, rewrite_in_quote));
+        if let Value::Symbol(s) = name {
+            scoped.insert(s.to_string());
+        }
+    }
+
+    let mut out = Vec::with_capacity(items.len());
+    out.push(items[0].clone());
+    out.push(Value::Vector(new_bindings));
+    for b in &items[2..] {
+        out.push(rewrite_form_impl(b, mangle, &scoped, rewrite_in_quote));
+    }
+    Value::List(out)
+}
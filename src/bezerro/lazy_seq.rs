@@ -0,0 +1,87 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::bezerro::env::Env;
+use crate::bezerro::error::EvalError;
+use crate::bezerro::value::Value;
+
+/// Computes the next element of a [`LazySeq`], or `None` once the sequence has ended.
+pub type LazyStep = Box<dyn FnMut(&Rc<RefCell<Env>>) -> Result<Option<Value>, EvalError>>;
+
+/// Sequence whose elements are computed on demand, used for `Value::LazySeq`.
+///
+/// Elements are realized in order and memoized: the step runs at most once per element, however
+/// many times (and through however many clones of the value) the sequence is read.
+pub struct LazySeq {
+    realized: RefCell<Vec<Value>>,
+    /// `None` once the step has reported the end of the sequence.
+    step: RefCell<Option<LazyStep>>,
+}
+
+impl LazySeq {
+    pub fn new(
+        step: impl FnMut(&Rc<RefCell<Env>>) -> Result<Option<Value>, EvalError> + 'static,
+    ) -> Self {
+        LazySeq {
+            realized: RefCell::new(Vec::new()),
+            step: RefCell::new(Some(Box::new(step))),
+        }
+    }
+
+    /// Returns the element at `index`, realizing every element before it first. `None` if the
+    /// sequence ends before `index`.
+    pub fn get(&self, index: usize, env: &Rc<RefCell<Env>>) -> Result<Option<Value>, EvalError> {
+        while self.realized.borrow().len() <= index {
+            let mut step = self.step.try_borrow_mut().map_err(|_| {
+                EvalError::Custom("lazy seq depends on its own elements".to_string())
+            })?;
+            let Some(next) = step.as_mut() else {
+                return Ok(None);
+            };
+            match next(env)? {
+                Some(value) => self.realized.borrow_mut().push(value),
+                None => *step = None,
+            }
+        }
+        Ok(Some(self.realized.borrow()[index].clone()))
+    }
+
+    /// The elements realized so far.
+    pub fn realized(&self) -> Ref<'_, Vec<Value>> {
+        self.realized.borrow()
+    }
+
+    /// Whether every element has been realized.
+    pub fn is_exhausted(&self) -> bool {
+        self.step.try_borrow().is_ok_and(|step| step.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn realizes_in_order_once_and_stops_at_the_end() {
+        let env = Rc::new(RefCell::new(Env::new()));
+        let calls = Rc::new(RefCell::new(0));
+        let counter = calls.clone();
+        let seq = LazySeq::new(move |_| {
+            *counter.borrow_mut() += 1;
+            let n = *counter.borrow();
+            Ok((n <= 3).then_some(Value::Int(n)))
+        });
+
+        assert_eq!(seq.get(1, &env).unwrap(), Some(Value::Int(2)));
+        assert_eq!(seq.get(0, &env).unwrap(), Some(Value::Int(1)));
+        assert_eq!(*calls.borrow(), 2);
+        assert!(!seq.is_exhausted());
+
+        assert_eq!(seq.get(5, &env).unwrap(), None);
+        assert!(seq.is_exhausted());
+        assert_eq!(
+            *seq.realized(),
+            vec![Value::Int(1), Value::Int(2), Value::Int(3)]
+        );
+    }
+}
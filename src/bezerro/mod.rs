@@ -2,10 +2,16 @@ pub mod builtins;
 pub mod env;
 pub mod error;
 pub mod eval;
+pub mod lazy_seq;
+pub mod ordered_map;
 pub mod value;
 
-pub use builtins::register_builtins;
+pub use builtins::{builtin_doc, help_text, register_builtins, BuiltinDoc};
 pub use env::{define_global, Env};
 pub use error::EvalError;
-pub use eval::{apply, eval, eval_value, node_to_form};
-pub use value::{BuiltinFn, Value};
+pub use eval::{
+    apply, eval, eval_file, eval_value, node_to_annotated_form, node_to_form, AnnotatedForm,
+};
+pub use lazy_seq::LazySeq;
+pub use ordered_map::OrderedMap;
+pub use value::{intern, BuiltinFn, NativeFn, PrettyOpts, Value};
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use crate::bezerro::value::Value;
+
+/// Map that remembers insertion order, used for `Value::Map`.
+///
+/// Iteration (and so printing, `use` rewriting and map bindings) follows the order keys were
+/// first inserted, which for literals is the order they were written in. Equality ignores order.
+#[derive(Debug, Clone, Default)]
+pub struct OrderedMap {
+    entries: Vec<(Value, Value)>,
+    index: HashMap<Value, usize>,
+}
+
+impl OrderedMap {
+    pub fn new() -> Self {
+        OrderedMap::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        OrderedMap {
+            entries: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.index.get(key).map(|&i| &self.entries[i].1)
+    }
+
+    pub fn contains_key(&self, key: &Value) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Inserts `value` under `key`. An existing key keeps its position and gets the new value;
+    /// the old value is returned.
+    pub fn insert(&mut self, key: Value, value: Value) -> Option<Value> {
+        match self.index.get(&key) {
+            Some(&i) => Some(std::mem::replace(&mut self.entries[i].1, value)),
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Value, &Value)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &Value> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+impl PartialEq for OrderedMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl Eq for OrderedMap {}
+
+impl FromIterator<(Value, Value)> for OrderedMap {
+    fn from_iter<I: IntoIterator<Item = (Value, Value)>>(iter: I) -> Self {
+        let mut map = OrderedMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl Extend<(Value, Value)> for OrderedMap {
+    fn extend<I: IntoIterator<Item = (Value, Value)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_insertion_order_and_position_on_overwrite() {
+        let mut map: OrderedMap = [(Value::Int(3), Value::Nil), (Value::Int(1), Value::Nil)]
+            .into_iter()
+            .collect();
+        map.insert(Value::Int(2), Value::Nil);
+        assert_eq!(
+            map.insert(Value::Int(3), Value::Bool(true)),
+            Some(Value::Nil)
+        );
+
+        let keys: Vec<_> = map.keys().cloned().collect();
+        assert_eq!(keys, vec![Value::Int(3), Value::Int(1), Value::Int(2)]);
+        assert_eq!(map.get(&Value::Int(3)), Some(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn equality_ignores_order() {
+        let a: OrderedMap = [
+            (Value::Int(1), Value::Int(10)),
+            (Value::Int(2), Value::Int(20)),
+        ]
+        .into_iter()
+        .collect();
+        let b: OrderedMap = [
+            (Value::Int(2), Value::Int(20)),
+            (Value::Int(1), Value::Int(10)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(a, b);
+    }
+}
@@ -2,22 +2,64 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 use crate::bezerro::env::Env;
 use crate::bezerro::error::EvalError;
+use crate::bezerro::lazy_seq::LazySeq;
+use crate::bezerro::ordered_map::OrderedMap;
 
 pub type BuiltinFn = fn(&[Value], &Rc<RefCell<Env>>) -> Result<Value, EvalError>;
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// A native function that closes over state, unlike [`BuiltinFn`].
+pub type NativeFn = Rc<dyn Fn(&[Value], &Rc<RefCell<Env>>) -> Result<Value, EvalError>>;
+
+thread_local! {
+    /// Names handed out by [`intern`]. Entries only hold weak references, so a name is freed once
+    /// no symbol or keyword uses it; dead entries are swept before the table grows.
+    static INTERNED: RefCell<HashMap<Box<str>, Weak<str>>> = RefCell::new(HashMap::new());
+}
+
+/// Returns a shared copy of `s`.
+///
+/// Identifiers repeat a lot in source code, so symbols and keywords share one allocation per
+/// distinct name instead of allocating at every reference.
+pub fn intern(s: &str) -> Rc<str> {
+    INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+        if let Some(shared) = interned.get(s).and_then(Weak::upgrade) {
+            return shared;
+        }
+        if interned.len() == interned.capacity() {
+            interned.retain(|_, name| name.strong_count() > 0);
+        }
+        let shared: Rc<str> = Rc::from(s);
+        interned.insert(s.into(), Rc::downgrade(&shared));
+        shared
+    })
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Keyword {
-    pub namespace: Option<String>,
-    pub name: String,
+    pub namespace: Option<Rc<str>>,
+    pub name: Rc<str>,
+}
+
+// Written out rather than derived: the derived impl hashes the `Option` discriminant, whose hashed
+// form std doesn't specify, and `hash` results should only depend on `FnvHasher`.
+impl Hash for Keyword {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.namespace.is_some().hash(state);
+        if let Some(namespace) = &self.namespace {
+            namespace.hash(state);
+        }
+        self.name.hash(state);
+    }
 }
 
 impl Keyword {
     pub fn is_bare(&self, name: &str) -> bool {
-        self.namespace.is_none() && self.name == name
+        self.namespace.is_none() && &*self.name == name
     }
 }
 
@@ -30,16 +72,27 @@ pub enum Value {
     Char(char),
     String(String),
     Keyword(Keyword),
-    Symbol(String),
+    Symbol(Rc<str>),
     List(Vec<Value>),
     Vector(Vec<Value>),
-    Map(Rc<HashMap<Value, Value>>),
+    Map(Rc<OrderedMap>),
     Set(Rc<HashSet<Value>>),
+    /// A possibly infinite sequence realized on demand (e.g. from `iterate`). Compares and hashes
+    /// by identity, since comparing contents could mean realizing forever.
+    LazySeq(Rc<LazySeq>),
+    /// A vector or map being built in place by `conj!`/`assoc!`. `persistent!` takes the
+    /// collection out, leaving `None` so later use of the transient is an error.
+    Transient(Rc<RefCell<Option<Value>>>),
     Recur(Vec<Value>),
     Builtin {
         name: &'static str,
         func: BuiltinFn,
     },
+    /// A function built at runtime by the interpreter (e.g. the wrapper installed by `trace`).
+    Native {
+        name: String,
+        func: NativeFn,
+    },
     Lambda {
         params: Vec<String>,
         body: Vec<Value>,
@@ -50,9 +103,44 @@ pub enum Value {
         body: Vec<Value>,
         env: Rc<RefCell<Env>>,
     },
+    /// A multimethod defined with `defmulti`. Its dispatch function and method table live in
+    /// the env's multimethod registry under `name`.
+    Multimethod {
+        name: String,
+    },
+}
+
+/// Layout options for [`Value::pretty`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyOpts {
+    /// Collections whose compact form fits in this many columns are printed on a single line.
+    pub width: usize,
+    /// Rounds floats to this many significant digits for display. `None` prints them exactly.
+    ///
+    /// This only affects [`Value::pretty`]; `Display` always prints floats round-trippably.
+    pub precision: Option<usize>,
+}
+
+impl Default for PrettyOpts {
+    fn default() -> Self {
+        PrettyOpts {
+            width: 80,
+            precision: None,
+        }
+    }
 }
 
 impl Value {
+    /// Renders the value for human reading.
+    ///
+    /// Collections that fit within `opts.width` stay inline (identical to `Display`); larger ones
+    /// put one element (or map entry) per line, aligned under the opening delimiter.
+    pub fn pretty(&self, opts: &PrettyOpts) -> String {
+        let mut out = String::new();
+        write_pretty(&mut out, self, 0, opts);
+        out
+    }
+
     pub fn type_name(&self) -> &'static str {
         match self {
             Value::Nil => "nil",
@@ -67,10 +155,27 @@ impl Value {
             Value::Vector(_) => "vector",
             Value::Map(_) => "map",
             Value::Set(_) => "set",
+            Value::LazySeq(_) => "lazy-seq",
+            Value::Transient(_) => "transient",
             Value::Recur(_) => "recur",
             Value::Builtin { .. } => "builtin",
+            Value::Native { .. } => "native",
             Value::Lambda { .. } => "lambda",
             Value::Macro { .. } => "macro",
+            Value::Multimethod { .. } => "multimethod",
+        }
+    }
+
+    /// Looks `key` up the way `get` and `(:key m)` do: maps (which also serve as records) by key,
+    /// `nil` as an empty map. Any other value is a type error.
+    pub fn lookup(&self, key: &Value) -> Result<Option<Value>, EvalError> {
+        match self {
+            Value::Map(m) => Ok(m.get(key).cloned()),
+            Value::Nil => Ok(None),
+            other => Err(EvalError::TypeError {
+                expected: "map",
+                got: other.type_name(),
+            }),
         }
     }
 
@@ -89,8 +194,20 @@ impl Value {
             _ => true,
         }
     }
+
+    /// Like `==`, but lists and vectors with equal elements compare equal, at any nesting depth.
+    pub fn seq_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::List(a) | Value::Vector(a), Value::List(b) | Value::Vector(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.seq_eq(y))
+            }
+            _ => self == other,
+        }
+    }
 }
 
+/// Structural equality. Lists and vectors are distinct types here, so `'(1 2)` is not equal to
+/// `[1 2]`; use [`Value::seq_eq`] to compare them element-wise.
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -98,8 +215,9 @@ impl PartialEq for Value {
             (Value::Bool(a), Value::Bool(b)) => a == b,
             (Value::Int(a), Value::Int(b)) => a == b,
             (Value::Float(a), Value::Float(b)) => float_eq(*a, *b),
-            (Value::Int(a), Value::Float(b)) => float_eq(*a as f64, *b),
-            (Value::Float(a), Value::Int(b)) => float_eq(*a, *b as f64),
+            (Value::Int(a), Value::Float(b)) | (Value::Float(b), Value::Int(a)) => {
+                float_as_int(*b) == Some(*a)
+            }
             (Value::Char(a), Value::Char(b)) => a == b,
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Keyword(a), Value::Keyword(b)) => a == b,
@@ -108,10 +226,13 @@ impl PartialEq for Value {
             (Value::Vector(a), Value::Vector(b)) => a == b,
             (Value::Set(a), Value::Set(b)) => a.as_ref() == b.as_ref(),
             (Value::Map(a), Value::Map(b)) => a.as_ref() == b.as_ref(),
+            (Value::LazySeq(a), Value::LazySeq(b)) => Rc::ptr_eq(a, b),
+            (Value::Transient(a), Value::Transient(b)) => Rc::ptr_eq(a, b),
             (Value::Recur(a), Value::Recur(b)) => a == b,
             (Value::Builtin { name: a, func: af }, Value::Builtin { name: b, func: bf }) => {
                 a == b && (*af as usize) == (*bf as usize)
             }
+            (Value::Native { func: af, .. }, Value::Native { func: bf, .. }) => Rc::ptr_eq(af, bf),
             (
                 Value::Lambda {
                     params: ap,
@@ -136,6 +257,7 @@ impl PartialEq for Value {
                     env: be,
                 },
             ) => ap == bp && ab == bb && Rc::ptr_eq(ae, be),
+            (Value::Multimethod { name: a }, Value::Multimethod { name: b }) => a == b,
             _ => false,
         }
     }
@@ -143,18 +265,28 @@ impl PartialEq for Value {
 
 impl Eq for Value {}
 
+// Values key maps and sets even though closures, lazy seqs and transients hold `RefCell`s. Hashing
+// and equality only look at those cells' pointers, never their contents, so a key can't change
+// under a collection; that is what makes the `clippy::mutable_key_type` allows sound.
 impl Hash for Value {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        use std::collections::hash_map::DefaultHasher;
-
-        // Hash the variant discriminant first so distinct variants don't collide easily.
-        std::mem::discriminant(self).hash(state);
+        // Hash the type name first so distinct variants don't collide easily (a name rather than
+        // `mem::discriminant`, whose hashed form std doesn't specify). Ints and floats are the
+        // exception: they compare equal across types (`1 == 1.0`), so they share one tag, and a
+        // float holding an exact int hashes as that int.
+        match self {
+            Value::Int(_) | Value::Float(_) => "number".hash(state),
+            _ => self.type_name().hash(state),
+        }
 
         match self {
             Value::Nil => {}
             Value::Bool(b) => b.hash(state),
             Value::Int(i) => i.hash(state),
-            Value::Float(f) => float_hash(*f).hash(state),
+            Value::Float(f) => match float_as_int(*f) {
+                Some(i) => i.hash(state),
+                None => float_hash(*f).hash(state),
+            },
             Value::Char(c) => c.hash(state),
             Value::String(s) => s.hash(state),
             Value::Keyword(k) => k.hash(state),
@@ -171,7 +303,7 @@ impl Hash for Value {
                 // Order-independent hashing: combine element hashes commutatively.
                 let mut acc: u64 = 0;
                 for item in items.as_ref() {
-                    let mut h = DefaultHasher::new();
+                    let mut h = FnvHasher::default();
                     item.hash(&mut h);
                     acc ^= h.finish();
                 }
@@ -182,18 +314,24 @@ impl Hash for Value {
 
                 // Order-independent hashing: combine entry hashes commutatively.
                 let mut acc: u64 = 0;
-                for (k, v) in entries.as_ref() {
-                    let mut h = DefaultHasher::new();
+                for (k, v) in entries.iter() {
+                    let mut h = FnvHasher::default();
                     k.hash(&mut h);
                     v.hash(&mut h);
                     acc ^= h.finish();
                 }
                 acc.hash(state);
             }
+            Value::LazySeq(seq) => Rc::as_ptr(seq).hash(state),
+            Value::Transient(t) => Rc::as_ptr(t).hash(state),
             Value::Builtin { name, func } => {
                 name.hash(state);
                 (*func as usize).hash(state);
             }
+            Value::Native { name, func } => {
+                name.hash(state);
+                (Rc::as_ptr(func) as *const () as usize).hash(state);
+            }
             Value::Lambda { params, body, env } => {
                 params.hash(state);
                 body.hash(state);
@@ -204,6 +342,7 @@ impl Hash for Value {
                 body.hash(state);
                 Rc::as_ptr(env).hash(state);
             }
+            Value::Multimethod { name } => name.hash(state),
         }
     }
 }
@@ -216,73 +355,183 @@ impl fmt::Debug for Value {
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Value::Nil => write!(f, "nil"),
-            Value::Bool(b) => write!(f, "{}", b),
-            Value::Int(n) => write!(f, "{}", n),
-            Value::Float(n) => {
-                if n.is_nan() {
-                    write!(f, "NaN")
-                } else if n.is_infinite() && n.is_sign_positive() {
-                    write!(f, "Infinity")
-                } else if n.is_infinite() && n.is_sign_negative() {
-                    write!(f, "-Infinity")
-                } else {
-                    write!(f, "{}", n)
-                }
-            }
-            Value::Char(c) => write!(f, "\\{}", c),
-            Value::String(s) => write!(f, "\"{}\"", escape_string(s)),
-            Value::Keyword(k) => write!(f, ":{}", format_keyword(k)),
-            Value::Symbol(s) => write!(f, "{s}"),
-            Value::List(items) => {
-                write!(f, "(")?;
-                write_joined(f, items)?;
-                write!(f, ")")
+        write_value(f, self, None)
+    }
+}
+
+/// Display adapter that rounds floats to `precision` significant digits.
+struct Rounded<'v> {
+    value: &'v Value,
+    precision: Option<usize>,
+}
+
+impl fmt::Display for Rounded<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_value(f, self.value, self.precision)
+    }
+}
+
+fn write_value(f: &mut fmt::Formatter<'_>, value: &Value, precision: Option<usize>) -> fmt::Result {
+    match value {
+        Value::Nil => write!(f, "nil"),
+        Value::Bool(b) => write!(f, "{}", b),
+        Value::Int(n) => write!(f, "{}", n),
+        Value::Float(n) => {
+            if n.is_nan() {
+                write!(f, "NaN")
+            } else if n.is_infinite() && n.is_sign_positive() {
+                write!(f, "Infinity")
+            } else if n.is_infinite() && n.is_sign_negative() {
+                write!(f, "-Infinity")
+            } else {
+                write!(f, "{}", round_significant(*n, precision))
             }
-            Value::Vector(items) => {
-                write!(f, "[")?;
-                write_joined(f, items)?;
-                write!(f, "]")
+        }
+        Value::Char(c) => write!(f, "\\{}", c),
+        Value::String(s) => write!(f, "\"{}\"", escape_string(s)),
+        Value::Keyword(k) => write!(f, ":{}", format_keyword(k)),
+        Value::Symbol(s) => write!(f, "{s}"),
+        Value::List(items) => {
+            write!(f, "(")?;
+            write_joined(f, items, precision)?;
+            write!(f, ")")
+        }
+        Value::Vector(items) => {
+            write!(f, "[")?;
+            write_joined(f, items, precision)?;
+            write!(f, "]")
+        }
+        Value::Map(entries) => {
+            write!(f, "{{")?;
+            for (i, (k, v)) in entries.iter().enumerate() {
+                if i != 0 {
+                    write!(f, " ")?;
+                }
+                write_value(f, k, precision)?;
+                write!(f, " ")?;
+                write_value(f, v, precision)?;
             }
-            Value::Map(entries) => {
-                write!(f, "{{")?;
-                let mut items: Vec<_> = entries.iter().collect();
-                items.sort_by(|(ka, _), (kb, _)| ka.to_string().cmp(&kb.to_string()));
-                for (i, (k, v)) in items.into_iter().enumerate() {
-                    if i != 0 {
-                        write!(f, " ")?;
-                    }
-                    write!(f, "{} {}", k, v)?;
+            write!(f, "}}")
+        }
+        Value::Set(items) => {
+            write!(f, "%{{")?;
+            let mut vec: Vec<_> = items.iter().collect();
+            vec.sort_by_key(|v| v.to_string());
+            for (i, item) in vec.into_iter().enumerate() {
+                if i != 0 {
+                    write!(f, " ")?;
                 }
-                write!(f, "}}")
+                write_value(f, item, precision)?;
             }
-            Value::Set(items) => {
-                write!(f, "%{{")?;
-                let mut vec: Vec<_> = items.iter().collect();
-                vec.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
-                for (i, item) in vec.into_iter().enumerate() {
-                    if i != 0 {
-                        write!(f, " ")?;
-                    }
-                    write!(f, "{}", item)?;
+            write!(f, "}}")
+        }
+        Value::LazySeq(seq) => {
+            // Only what has been realized is shown; the rest would require running the step.
+            let realized = seq.realized();
+            write!(f, "(")?;
+            write_joined(f, &realized, precision)?;
+            if !seq.is_exhausted() {
+                let sep = if realized.is_empty() { "" } else { " " };
+                write!(f, "{sep}...")?;
+            }
+            write!(f, ")")
+        }
+        Value::Transient(t) => match &*t.borrow() {
+            Some(coll) => write!(f, "#<transient {}>", coll.type_name()),
+            None => write!(f, "#<transient>"),
+        },
+        Value::Recur(_) => write!(f, "#<recur>"),
+        Value::Builtin { name, .. } => write!(f, "#<builtin {name}>"),
+        Value::Native { name, .. } => write!(f, "#<native {name}>"),
+        Value::Lambda { params, .. } => write!(f, "#<fn ({})>", params.join(" ")),
+        Value::Macro { params, .. } => write!(f, "#<macro ({})>", params.join(" ")),
+        Value::Multimethod { name } => write!(f, "#<multimethod {name}>"),
+    }
+}
+
+/// Rounds `n` to `precision` significant digits (`None` keeps it exact).
+fn round_significant(n: f64, precision: Option<usize>) -> f64 {
+    let Some(precision) = precision else {
+        return n;
+    };
+    // Formatting in scientific notation rounds to the requested number of significant digits;
+    // parsing it back lets `{}` print the shortest representation of the rounded value.
+    format!("{:.*e}", precision.max(1) - 1, n)
+        .parse()
+        .unwrap_or(n)
+}
+
+fn write_pretty(out: &mut String, value: &Value, column: usize, opts: &PrettyOpts) {
+    let compact = Rounded {
+        value,
+        precision: opts.precision,
+    }
+    .to_string();
+    if column + compact.len() <= opts.width {
+        out.push_str(&compact);
+        return;
+    }
+
+    match value {
+        Value::List(items) => write_pretty_seq(out, "(", items.iter(), ")", column, opts),
+        Value::Vector(items) => write_pretty_seq(out, "[", items.iter(), "]", column, opts),
+        Value::Set(items) => {
+            let mut items: Vec<_> = items.iter().collect();
+            items.sort_by_key(|v| v.to_string());
+            write_pretty_seq(out, "%{", items.into_iter(), "}", column, opts);
+        }
+        Value::Map(entries) => {
+            out.push('{');
+            let child_column = column + 1;
+            for (i, (k, v)) in entries.iter().enumerate() {
+                if i != 0 {
+                    push_newline(out, child_column);
                 }
-                write!(f, "}}")
+                let key = k.to_string();
+                out.push_str(&key);
+                out.push(' ');
+                write_pretty(out, v, child_column + key.len() + 1, opts);
             }
-            Value::Recur(_) => write!(f, "#<recur>"),
-            Value::Builtin { name, .. } => write!(f, "#<builtin {name}>"),
-            Value::Lambda { params, .. } => write!(f, "#<fn ({})>", params.join(" ")),
-            Value::Macro { params, .. } => write!(f, "#<macro ({})>", params.join(" ")),
+            out.push('}');
+        }
+        _ => out.push_str(&compact),
+    }
+}
+
+fn write_pretty_seq<'v>(
+    out: &mut String,
+    open: &str,
+    items: impl Iterator<Item = &'v Value>,
+    close: &str,
+    column: usize,
+    opts: &PrettyOpts,
+) {
+    out.push_str(open);
+    let child_column = column + open.len();
+    for (i, item) in items.enumerate() {
+        if i != 0 {
+            push_newline(out, child_column);
         }
+        write_pretty(out, item, child_column, opts);
     }
+    out.push_str(close);
+}
+
+fn push_newline(out: &mut String, column: usize) {
+    out.push('\n');
+    out.extend(std::iter::repeat_n(' ', column));
 }
 
-fn write_joined(f: &mut fmt::Formatter<'_>, items: &[Value]) -> fmt::Result {
+fn write_joined(
+    f: &mut fmt::Formatter<'_>,
+    items: &[Value],
+    precision: Option<usize>,
+) -> fmt::Result {
     for (i, item) in items.iter().enumerate() {
         if i != 0 {
             write!(f, " ")?;
         }
-        write!(f, "{}", item)?;
+        write_value(f, item, precision)?;
     }
     Ok(())
 }
@@ -330,6 +579,70 @@ fn escape_string(s: &str) -> String {
     out
 }
 
+/// 64-bit FNV-1a. Unlike `DefaultHasher`, whose algorithm std may change between releases, its
+/// output is fully specified, so `hash` gives the same ints on every build and platform: integers
+/// are fed in little-endian and `usize` as a `u64`.
+pub(crate) struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64);
+    }
+}
+
 fn float_hash(f: f64) -> u64 {
     // Ensure hashing is consistent with equality:
     // - treat +0.0 and -0.0 as equal
@@ -346,3 +659,111 @@ fn float_hash(f: f64) -> u64 {
 fn float_eq(a: f64, b: f64) -> bool {
     float_hash(a) == float_hash(b)
 }
+
+/// The int `f` is exactly equal to, if any. Comparing through this instead of `i as f64` keeps
+/// ints beyond 2^53, which don't survive the conversion to float, distinct.
+fn float_as_int(f: f64) -> Option<i64> {
+    // `i64::MIN` is exactly representable but `i64::MAX` isn't, hence the asymmetric bounds.
+    (f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64).then_some(f as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kw(name: &str) -> Value {
+        Value::Keyword(Keyword {
+            namespace: None,
+            name: intern(name),
+        })
+    }
+
+    fn map(entries: Vec<(Value, Value)>) -> Value {
+        Value::Map(Rc::new(entries.into_iter().collect()))
+    }
+
+    #[test]
+    fn interned_names_are_shared_and_freed_when_unused() {
+        let a = intern("shared-name");
+        assert!(Rc::ptr_eq(&a, &intern("shared-name")));
+
+        for i in 0..10_000 {
+            intern(&format!("temp-{i}"));
+        }
+        let live = INTERNED.with(|interned| interned.borrow().len());
+        assert!(live < 100, "{live} names still interned");
+        assert!(Rc::ptr_eq(&a, &intern("shared-name")));
+    }
+
+    #[test]
+    fn pretty_keeps_small_collections_inline() {
+        let v = map(vec![
+            (kw("a"), Value::Int(1)),
+            (kw("b"), Value::Vector(vec![Value::Int(2), Value::Int(3)])),
+        ]);
+        assert_eq!(v.pretty(&PrettyOpts::default()), "{:a 1 :b [2 3]}");
+        assert_eq!(v.pretty(&PrettyOpts::default()), v.to_string());
+    }
+
+    #[test]
+    fn pretty_indents_large_nested_structures() {
+        let v = map(vec![
+            (
+                kw("items"),
+                Value::Vector(vec![
+                    map(vec![(kw("id"), Value::Int(1)), (kw("tags"), kw("x"))]),
+                    map(vec![(kw("id"), Value::Int(2)), (kw("tags"), kw("y"))]),
+                ]),
+            ),
+            (
+                kw("name"),
+                Value::String("a fairly long string value".to_string()),
+            ),
+        ]);
+        let expected = "\
+{:items [{:id 1 :tags :x}
+         {:id 2 :tags :y}]
+ :name \"a fairly long string value\"}";
+        let opts = PrettyOpts {
+            width: 40,
+            ..PrettyOpts::default()
+        };
+        assert_eq!(v.pretty(&opts), expected);
+    }
+
+    #[test]
+    fn pretty_precision_rounds_floats_for_display_only() {
+        let v = Value::Vector(vec![
+            Value::Float(0.1 + 0.2),
+            Value::Float(2.0 / 3.0),
+            Value::Float(f64::INFINITY),
+            Value::Float(f64::NAN),
+            Value::Int(7),
+        ]);
+        let opts = PrettyOpts {
+            precision: Some(6),
+            ..PrettyOpts::default()
+        };
+        assert_eq!(v.pretty(&opts), "[0.3 0.666667 Infinity NaN 7]");
+        assert_eq!(
+            v.to_string(),
+            "[0.30000000000000004 0.6666666666666666 Infinity NaN 7]"
+        );
+        assert_eq!(
+            v.pretty(&PrettyOpts::default()),
+            v.to_string(),
+            "no precision means exact output"
+        );
+    }
+
+    #[test]
+    fn fnv_hasher_matches_the_reference_vectors() {
+        let fnv = |bytes: &[u8]| {
+            let mut h = FnvHasher::default();
+            h.write(bytes);
+            h.finish()
+        };
+        assert_eq!(fnv(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
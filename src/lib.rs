@@ -9,4 +9,7 @@
 pub mod bezerro;
 pub mod vedn;
 
-pub use vedn::{parse, Error, ErrorKind, Keyword, Kind, Node, Parser, Span, Str, Symbol};
+pub use vedn::{
+    parse, parse_bytes, render_diagnostic, Error, ErrorKind, Keyword, Kind, Node, Parser,
+    ParserConfig, Span, Str, Symbol,
+};
@@ -2,11 +2,14 @@ use std::cell::RefCell;
 use std::env;
 use std::fs;
 use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::thread;
 
-use vaca::bezerro::{eval, register_builtins, Env, EvalError, Value};
-use vaca::ErrorKind;
+use vaca::bezerro::{
+    eval, eval_file, help_text, register_builtins, Env, EvalError, PrettyOpts, Value,
+};
+use vaca::{render_diagnostic, ErrorKind, Parser, ParserConfig, Span};
 
 fn main() {
     match env::args().nth(1).as_deref() {
@@ -22,7 +25,7 @@ fn make_global_env() -> Rc<RefCell<Env>> {
 }
 
 fn run_file(path: &str) {
-    let input = match fs::read_to_string(path) {
+    let input = match fs::read(path) {
         Ok(s) => s,
         Err(err) => {
             eprintln!("failed to read {path}: {err}");
@@ -33,13 +36,19 @@ fn run_file(path: &str) {
     // Run user code on a larger stack so deep recursion doesn't crash the process before we can
     // return a proper EvalError::StackOverflow.
     let source_dir = std::path::Path::new(path).parent().map(|p| p.to_path_buf());
+    let origin = path.to_string();
     let result: Result<Option<String>, String> = thread::Builder::new()
         .name("vaca-eval".to_string())
         .stack_size(64 * 1024 * 1024)
         .spawn(move || {
-            let forms = match vaca::parse(&input) {
+            // Only lossy (and so only copied) when parsing fails on invalid UTF-8.
+            let text = String::from_utf8_lossy(&input);
+            let forms = match vaca::parse_bytes(&input) {
                 Ok(nodes) => nodes,
-                Err(err) => return Err(err.to_string()),
+                Err(err) => {
+                    let message = err.kind.to_string();
+                    return Err(render_diagnostic(&text, &origin, err.span, &message));
+                }
             };
 
             let env = make_global_env();
@@ -48,9 +57,10 @@ fn run_file(path: &str) {
             }
             let mut last = Value::Nil;
             for form in &forms {
+                // Errors don't carry spans yet, so point at the top-level form that failed.
                 match eval(form, &env) {
                     Ok(v) => last = v,
-                    Err(e) => return Err(e.to_string()),
+                    Err(e) => return Err(render_eval_error(&e, &text, &origin, form.span)),
                 }
             }
             Ok((!matches!(last, Value::Nil)).then(|| last.to_string()))
@@ -79,8 +89,26 @@ fn run_file(path: &str) {
     }
 }
 
+/// Renders `e` against the top-level form that raised it, except for syntax errors in a file that
+/// form loaded, which point into that file instead.
+fn render_eval_error(e: &EvalError, input: &str, origin: &str, span: Span) -> String {
+    if let EvalError::ParseError {
+        path: Some(path),
+        error,
+    } = e
+    {
+        if let Ok(src) = fs::read_to_string(path) {
+            let message = error.kind.to_string();
+            return render_diagnostic(&src, &path.display().to_string(), error.span, &message);
+        }
+    }
+    render_diagnostic(input, origin, span, &e.to_string())
+}
+
 fn run_repl() {
     let env = make_global_env();
+    load_init_files(&env);
+    let mut opts = PrettyOpts::default();
     let mut buffer = String::new();
     let stdin = io::stdin();
     let mut stdin = stdin.lock();
@@ -107,9 +135,24 @@ fn run_repl() {
             break; // EOF
         }
 
+        if buffer.is_empty() {
+            if let Some(arg) = line.trim().strip_prefix(":precision") {
+                set_precision(&mut opts, arg.trim());
+                continue;
+            }
+            if let Some(name) = line.trim().strip_prefix(":help") {
+                match help_text(name.trim(), &env) {
+                    Some(text) => println!("{text}"),
+                    None if name.trim().is_empty() => eprintln!("usage: :help <name>"),
+                    None => eprintln!("undefined symbol: {}", name.trim()),
+                }
+                continue;
+            }
+        }
+
         buffer.push_str(&line);
 
-        let forms = match vaca::parse(&buffer) {
+        let forms = match Parser::with_config(&buffer, ParserConfig::untrusted()).parse_all() {
             Ok(nodes) => nodes,
             Err(err) => {
                 if is_incomplete(&err.kind) {
@@ -139,12 +182,53 @@ fn run_repl() {
         }
 
         if !matches!(last, Value::Nil) {
-            println!("{last}");
+            println!("{}", last.pretty(&opts));
         }
         buffer.clear();
     }
 }
 
+/// Evaluates the user's init files into the REPL env so they can predefine helpers. Errors are
+/// reported but don't stop the REPL.
+fn load_init_files(env: &Rc<RefCell<Env>>) {
+    for path in init_file_candidates() {
+        if !path.is_file() {
+            continue;
+        }
+        if let Err(e) = eval_file(&path, env) {
+            eprintln!("error in {}: {e}", path.display());
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/vaca/init.vaca` (or `~/.config/vaca/init.vaca`), then `.vacarc` in the
+/// current directory, so project settings can override user ones.
+fn init_file_candidates() -> Vec<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    config_dir
+        .map(|dir| dir.join("vaca").join("init.vaca"))
+        .into_iter()
+        .chain([PathBuf::from(".vacarc")])
+        .collect()
+}
+
+/// Handles the `:precision N` / `:precision off` REPL command.
+fn set_precision(opts: &mut PrettyOpts, arg: &str) {
+    match arg {
+        "" => match opts.precision {
+            Some(n) => println!("floats are shown with {n} significant digits"),
+            None => println!("floats are shown exactly"),
+        },
+        "off" => opts.precision = None,
+        _ => match arg.parse::<usize>() {
+            Ok(n) if n > 0 => opts.precision = Some(n),
+            _ => eprintln!("usage: :precision <digits> | :precision off"),
+        },
+    }
+}
+
 fn is_incomplete(kind: &ErrorKind) -> bool {
     matches!(
         kind,
@@ -23,11 +23,13 @@ pub struct Cursor<'a> {
 
 impl<'a> Cursor<'a> {
     /// Creates a new cursor over the provided input.
+    ///
+    /// A leading UTF-8 byte order mark is skipped; offsets still index into `input`.
     pub fn new(input: &'a str) -> Self {
         Cursor {
             input,
             bytes: input.as_bytes(),
-            index: 0,
+            index: if input.starts_with('\u{feff}') { 3 } else { 0 },
             line: 1,
             column: 1,
         }
@@ -59,6 +59,49 @@ pub enum ErrorKind {
     InvalidCharacterLiteral,
     /// A `\uNNNN` escape was malformed or out of range.
     InvalidUnicodeEscape,
+    /// The input crossed a limit configured in [`ParserConfig`](super::parser::ParserConfig).
+    LimitExceeded {
+        /// The configured limit that was crossed.
+        limit: usize,
+    },
+    /// The input passed to [`parse_bytes`](super::parser::parse_bytes) is not valid UTF-8.
+    InvalidUtf8,
+    /// Collections or `#` dispatches were nested deeper than
+    /// [`ParserConfig::max_depth`](super::parser::ParserConfig::max_depth).
+    NestingTooDeep {
+        /// The configured depth limit.
+        limit: usize,
+    },
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ErrorKind::UnexpectedChar { found, expected } => {
+                write!(f, "unexpected character {found:?}, expected {expected}")
+            }
+            ErrorKind::InvalidDispatch => write!(f, "invalid `#` dispatch"),
+            ErrorKind::InvalidSymbol => write!(f, "invalid symbol"),
+            ErrorKind::UnterminatedSymbol => write!(f, "unterminated quoted symbol"),
+            ErrorKind::InvalidKeyword => write!(f, "invalid keyword"),
+            ErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            ErrorKind::UnterminatedCollection { expected } => {
+                write!(f, "unterminated collection, expected `{expected}`")
+            }
+            ErrorKind::MapOddNumberOfForms => write!(f, "map literal has an odd number of forms"),
+            ErrorKind::InvalidCharacterLiteral => write!(f, "invalid character literal"),
+            ErrorKind::InvalidUnicodeEscape => write!(f, "invalid unicode escape"),
+            ErrorKind::LimitExceeded { limit } => {
+                write!(f, "input exceeds the configured limit of {limit}")
+            }
+            ErrorKind::InvalidUtf8 => write!(f, "input is not valid UTF-8"),
+            ErrorKind::NestingTooDeep { limit } => {
+                write!(f, "nesting exceeds the depth limit of {limit}")
+            }
+        }
+    }
 }
 
 /// A parsing error with source location.
@@ -92,10 +135,55 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{:?} at {}:{} ({}..{})",
+            "{} at {}:{} ({}..{})",
             self.kind, self.line, self.column, self.span.start, self.span.end
         )
     }
 }
 
 impl std::error::Error for Error {}
+
+/// Renders a diagnostic that quotes the source line containing `span` and underlines it.
+///
+/// `origin` names the source (usually a file path). Spans covering several lines are
+/// underlined up to the end of their first line.
+///
+/// ```text
+/// error: type error: expected number, got string
+///  --> main.vaca:2:1
+///   |
+/// 2 | (+ 1 "a")
+///   | ^^^^^^^^^
+/// ```
+pub fn render_diagnostic(source: &str, origin: &str, span: Span, message: &str) -> String {
+    let start = floor_char_boundary(source, span.start);
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |i| start + i);
+    let line_text = source[line_start..line_end].trim_end_matches('\r');
+    let line = source[..start].matches('\n').count() + 1;
+    let column = source[line_start..start].chars().count() + 1;
+
+    let end = floor_char_boundary(source, span.end.clamp(start, line_end));
+    let width = source[start..end].chars().count().max(1);
+
+    let gutter = " ".repeat(line.to_string().len());
+    format!(
+        "error: {message}\n\
+         {gutter}--> {origin}:{line}:{column}\n\
+         {gutter} |\n\
+         {line} | {line_text}\n\
+         {gutter} | {pad}{carets}",
+        pad = " ".repeat(column - 1),
+        carets = "^".repeat(width),
+    )
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    index = index.min(s.len());
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
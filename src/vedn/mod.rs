@@ -10,7 +10,8 @@
 //! stages (type checking, macro expansion, runtime, STL).
 //!
 //! # API
-//! Use [`parse`] to parse an input string into a sequence of EDN nodes.
+//! Use [`parse`] to parse an input string into a sequence of EDN nodes, or [`parse_bytes`]
+//! for raw bytes that still need UTF-8 validation.
 //!
 //! The public AST types are in [`value`], and errors/spans are in [`error`].
 
@@ -19,6 +20,6 @@ pub mod error;
 pub mod parser;
 pub mod value;
 
-pub use error::{Error, ErrorKind, Span};
-pub use parser::{parse, Parser};
+pub use error::{render_diagnostic, Error, ErrorKind, Span};
+pub use parser::{parse, parse_bytes, Parser, ParserConfig, DEFAULT_MAX_DEPTH};
 pub use value::{Keyword, Kind, Node, Number, NumberSuffix, Str, Symbol};
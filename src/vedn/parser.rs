@@ -7,7 +7,8 @@ use super::{
 /// Parses all top-level EDN elements from `input`.
 ///
 /// EDN has no mandatory top-level delimiter. This function therefore returns a
-/// sequence of nodes.
+/// sequence of nodes. A leading UTF-8 byte order mark is skipped, so spans of
+/// files saved with one start at byte 3.
 ///
 /// # Annotated forms
 /// Annotated elements (`#<form> <form>`) are preserved as [`Node::annotation`].
@@ -16,6 +17,59 @@ pub fn parse(input: &str) -> Result<Vec<Node<'_>>, Error> {
     Parser::new(input).parse_all()
 }
 
+/// Parses raw bytes, such as a file's contents, into a sequence of EDN nodes.
+///
+/// Fails with [`ErrorKind::InvalidUtf8`] pointing at the first invalid byte. Like [`parse`], a
+/// leading UTF-8 byte order mark is skipped.
+pub fn parse_bytes(input: &[u8]) -> Result<Vec<Node<'_>>, Error> {
+    let input = std::str::from_utf8(input).map_err(|e| {
+        let valid = &input[..e.valid_up_to()];
+        let end = e.valid_up_to() + e.error_len().unwrap_or(input.len() - e.valid_up_to());
+        let line_start = valid.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+        Error::new(
+            ErrorKind::InvalidUtf8,
+            Span::new(e.valid_up_to(), end),
+            valid.iter().filter(|&&b| b == b'\n').count() as u32 + 1,
+            (valid.len() - line_start) as u32 + 1,
+        )
+    })?;
+    parse(input)
+}
+
+/// Nesting limit of [`ParserConfig::untrusted`], low enough that parsing can't overflow the stack.
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+/// Resource limits for parsing untrusted input.
+///
+/// All limits default to `None` (unlimited), which suits source files; use
+/// [`ParserConfig::untrusted`] for input from users or other programs. Crossing a size limit
+/// fails with [`ErrorKind::LimitExceeded`], and crossing the depth limit with
+/// [`ErrorKind::NestingTooDeep`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub struct ParserConfig {
+    /// Maximum number of nodes in the whole document, counting annotations and
+    /// discarded forms.
+    pub max_nodes: Option<usize>,
+    /// Maximum number of forms inside a single collection. Each map entry
+    /// counts as two forms (key and value).
+    pub max_collection_len: Option<usize>,
+    /// Maximum nesting of collections and `#` dispatches (annotations and discards).
+    /// `None` disables the check, which lets deeply nested input overflow the stack.
+    pub max_depth: Option<usize>,
+}
+
+impl ParserConfig {
+    /// Limits for input that isn't trusted source code: nesting is bounded by
+    /// [`DEFAULT_MAX_DEPTH`] so deeply nested input fails with an error instead of overflowing
+    /// the stack.
+    pub fn untrusted() -> Self {
+        ParserConfig {
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            ..ParserConfig::default()
+        }
+    }
+}
+
 /// Streaming EDN parser.
 ///
 /// The parser reads directly from the input string (no token buffering) and
@@ -25,13 +79,24 @@ pub fn parse(input: &str) -> Result<Vec<Node<'_>>, Error> {
 #[derive(Debug, Clone)]
 pub struct Parser<'a> {
     cursor: Cursor<'a>,
+    config: ParserConfig,
+    node_count: usize,
+    depth: usize,
 }
 
 impl<'a> Parser<'a> {
     /// Creates a new parser over `input`.
     pub fn new(input: &'a str) -> Self {
+        Parser::with_config(input, ParserConfig::default())
+    }
+
+    /// Creates a new parser over `input` that enforces the limits in `config`.
+    pub fn with_config(input: &'a str, config: ParserConfig) -> Self {
         Parser {
             cursor: Cursor::new(input),
+            config,
+            node_count: 0,
+            depth: 0,
         }
     }
 
@@ -50,29 +115,46 @@ impl<'a> Parser<'a> {
         Ok(nodes)
     }
 
+    /// Parses all top-level elements until EOF, pairing each with its source span.
+    ///
+    /// Each span is tight: it covers exactly the form's text (including any leading
+    /// `#<annotation>`), never the separators or comments around it. Spans are
+    /// non-overlapping and appear in source order.
+    pub fn parse_all_with_spans(self) -> Result<Vec<(Span, Node<'a>)>, Error> {
+        Ok(self
+            .parse_all()?
+            .into_iter()
+            .map(|node| (node.span, node))
+            .collect())
+    }
+
     fn parse_form(&mut self) -> Result<Option<Node<'a>>, Error> {
         self.cursor.skip_ws_and_comments();
         let Some(b) = self.cursor.peek() else {
             return Err(self.cursor.error_here(ErrorKind::UnexpectedEof));
         };
 
-        match b {
-            b'(' => Ok(Some(self.parse_list()?)),
-            b'[' => Ok(Some(self.parse_vector()?)),
-            b'{' => Ok(Some(self.parse_map()?)),
+        if b == b'#' {
+            return self.nested(Self::parse_dispatch);
+        }
+        let node = match b {
+            b'(' => self.nested(Self::parse_list)?,
+            b'[' => self.nested(Self::parse_vector)?,
+            b'{' => self.nested(Self::parse_map)?,
             b'%' => {
                 if self.cursor.peek_next() == Some(b'{') {
-                    Ok(Some(self.parse_set()?))
+                    self.nested(Self::parse_set)?
                 } else {
-                    Ok(Some(self.parse_token()?))
+                    self.parse_token()?
                 }
             }
-            b'"' => Ok(Some(self.parse_string()?)),
-            b':' => Ok(Some(self.parse_keyword_node()?)),
-            b'\\' => Ok(Some(self.parse_char()?)),
-            b'#' => self.parse_dispatch(),
-            _ => Ok(Some(self.parse_token()?)),
-        }
+            b'"' => self.parse_string()?,
+            b':' => self.parse_keyword_node()?,
+            b'\\' => self.parse_char()?,
+            _ => self.parse_token()?,
+        };
+        self.count_node(&node)?;
+        Ok(Some(node))
     }
 
     /// Parses a single form without skipping leading separators.
@@ -84,22 +166,61 @@ impl<'a> Parser<'a> {
             return Err(self.cursor.error_here(ErrorKind::UnexpectedEof));
         };
 
-        match b {
-            b'(' => Ok(Some(self.parse_list()?)),
-            b'[' => Ok(Some(self.parse_vector()?)),
-            b'{' => Ok(Some(self.parse_map()?)),
+        if b == b'#' {
+            return self.nested(Self::parse_dispatch);
+        }
+        let node = match b {
+            b'(' => self.nested(Self::parse_list)?,
+            b'[' => self.nested(Self::parse_vector)?,
+            b'{' => self.nested(Self::parse_map)?,
             b'%' => {
                 if self.cursor.peek_next() == Some(b'{') {
-                    Ok(Some(self.parse_set()?))
+                    self.nested(Self::parse_set)?
                 } else {
-                    Ok(Some(self.parse_token()?))
+                    self.parse_token()?
                 }
             }
-            b'"' => Ok(Some(self.parse_string()?)),
-            b':' => Ok(Some(self.parse_keyword_node()?)),
-            b'\\' => Ok(Some(self.parse_char()?)),
-            b'#' => self.parse_dispatch(),
-            _ => Ok(Some(self.parse_token()?)),
+            b'"' => self.parse_string()?,
+            b':' => self.parse_keyword_node()?,
+            b'\\' => self.parse_char()?,
+            _ => self.parse_token()?,
+        };
+        self.count_node(&node)?;
+        Ok(Some(node))
+    }
+
+    /// Runs `parse` one nesting level deeper, enforcing [`ParserConfig::max_depth`].
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+        if let Some(limit) = self.config.max_depth {
+            if self.depth >= limit {
+                return Err(self.cursor.error_here(ErrorKind::NestingTooDeep { limit }));
+            }
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Counts a parsed node against [`ParserConfig::max_nodes`].
+    fn count_node(&mut self, node: &Node<'a>) -> Result<(), Error> {
+        self.node_count += 1;
+        match self.config.max_nodes {
+            Some(limit) if self.node_count > limit => Err(self
+                .cursor
+                .error_span(ErrorKind::LimitExceeded { limit }, node.span)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks a collection's length against [`ParserConfig::max_collection_len`].
+    fn check_collection_len(&self, start: usize, len: usize) -> Result<(), Error> {
+        match self.config.max_collection_len {
+            Some(limit) if len > limit => Err(self.cursor.error_span(
+                ErrorKind::LimitExceeded { limit },
+                Span::new(start, self.cursor.index),
+            )),
+            _ => Ok(()),
         }
     }
 
@@ -125,6 +246,7 @@ impl<'a> Parser<'a> {
                 _ => {
                     if let Some(v) = self.parse_form()? {
                         values.push(v);
+                        self.check_collection_len(start, values.len())?;
                     }
                 }
             }
@@ -155,6 +277,7 @@ impl<'a> Parser<'a> {
                 _ => {
                     if let Some(v) = self.parse_form()? {
                         values.push(v);
+                        self.check_collection_len(start, values.len())?;
                     }
                 }
             }
@@ -190,6 +313,7 @@ impl<'a> Parser<'a> {
                 _ => {
                     if let Some(item) = self.parse_form()? {
                         items.push(item);
+                        self.check_collection_len(start, items.len())?;
                     }
                 }
             }
@@ -238,6 +362,7 @@ impl<'a> Parser<'a> {
                 _ => {
                     if let Some(v) = self.parse_form()? {
                         values.push(v);
+                        self.check_collection_len(start, values.len())?;
                     }
                 }
             }
@@ -287,13 +412,21 @@ impl<'a> Parser<'a> {
                 form.span = self.cursor.span_from(start);
 
                 // Attach annotation. If the form is already annotated (e.g. `#a #b x`),
-                // preserve both by collecting them into a list in source order.
+                // preserve all of them by collecting them into one flat list in source order.
                 form.annotation = Some(Box::new(match form.annotation.take() {
                     None => annotation,
+                    Some(mut prev) if prev.merged_annotations => {
+                        prev.span.start = annotation.span.start;
+                        if let Kind::List(items) = &mut prev.kind {
+                            items.insert(0, annotation);
+                        }
+                        *prev
+                    }
                     Some(prev) => {
-                        let prev = *prev;
-                        let span = Span::new(prev.span.start, annotation.span.end);
-                        Node::new(span, Kind::List(vec![prev, annotation]))
+                        let span = Span::new(annotation.span.start, prev.span.end);
+                        let mut merged = Node::new(span, Kind::List(vec![annotation, *prev]));
+                        merged.merged_annotations = true;
+                        merged
                     }
                 }));
 
@@ -460,8 +593,7 @@ impl<'a> Parser<'a> {
             "true" => Ok(Node::new(span, Kind::Bool(true))),
             "false" => Ok(Node::new(span, Kind::Bool(false))),
             _ => {
-                if token.ends_with(':') {
-                    let base = &token[..token.len() - 1];
+                if let Some(base) = token.strip_suffix(':') {
                     let analysis = analyze_symbol_token(base).map_err(|kind| {
                         self.cursor
                             .error_span(kind, Span::new(token_start, self.cursor.index))
@@ -554,14 +686,13 @@ fn parse_keyword_token(token: &str) -> Result<Keyword<'_>, ErrorKind> {
         return Err(ErrorKind::InvalidKeyword);
     }
 
-    if token.starts_with(':') {
+    if let Some(without_colon) = token.strip_prefix(':') {
         // Leading-colon keywords: `:name`, `:ns/name`, `:`...`/...`
         if token.starts_with("::") || token.starts_with(":/") {
             return Err(ErrorKind::InvalidKeyword);
         }
 
         let raw = token;
-        let without_colon = &token[1..];
         let symbol = parse_symbol(without_colon).map_err(|_| ErrorKind::InvalidKeyword)?;
         return Ok(Keyword {
             raw,
@@ -570,9 +701,8 @@ fn parse_keyword_token(token: &str) -> Result<Keyword<'_>, ErrorKind> {
         });
     }
 
-    if token.ends_with(':') {
+    if let Some(base) = token.strip_suffix(':') {
         // Trailing-colon keywords: `name:`, `ns/name:`
-        let base = &token[..token.len() - 1];
         if base.is_empty() {
             return Err(ErrorKind::InvalidKeyword);
         }
@@ -913,6 +1043,30 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn number_sign_accessors_read_the_lexeme() {
+        let values = parse("-5 +5 0 -1.5 -0 0.0e3 2.5M").unwrap();
+        let numbers: Vec<&Number<'_>> = values
+            .iter()
+            .map(|v| match &v.kind {
+                Kind::Number(n) => n,
+                other => panic!("expected number, got: {other:?}"),
+            })
+            .collect();
+
+        let signs: Vec<i8> = numbers.iter().map(|n| n.sign()).collect();
+        assert_eq!(signs, [-1, 1, 0, -1, 0, 0, 1]);
+
+        let negative: Vec<bool> = numbers.iter().map(|n| n.is_negative()).collect();
+        assert_eq!(negative, [true, false, false, true, false, false, false]);
+
+        let zero: Vec<bool> = numbers.iter().map(|n| n.is_zero()).collect();
+        assert_eq!(zero, [false, false, true, false, true, true, false]);
+
+        let integer: Vec<bool> = numbers.iter().map(|n| n.is_integer()).collect();
+        assert_eq!(integer, [true, true, true, false, true, false, false]);
+    }
+
     #[test]
     fn parse_collections() {
         let values = parse("(a 1) [a 1] {:a 1, :b 2} %{a b}").unwrap();
@@ -1170,6 +1324,234 @@ mod tests {
         assert_eq!(param0_name.name, "a");
     }
 
+    #[test]
+    fn parse_all_with_spans_slices_back_to_exact_source() {
+        let input = "  (def x 1) ; comment\n,,#int y\n## skipped [a b]\n#a #b {:k \"v\"}  %{1 2}\n";
+        let forms = Parser::new(input).parse_all_with_spans().unwrap();
+        let texts: Vec<&str> = forms
+            .iter()
+            .map(|(span, _)| &input[span.start..span.end])
+            .collect();
+        assert_eq!(
+            texts,
+            ["(def x 1)", "#int y", "[a b]", "#a #b {:k \"v\"}", "%{1 2}"]
+        );
+
+        for window in forms.windows(2) {
+            assert!(window[0].0.end <= window[1].0.start);
+        }
+        for (span, node) in &forms {
+            assert_eq!(*span, node.span);
+        }
+    }
+
+    /// Moves every span in `node` back by `offset`, as if it had been parsed from
+    /// `src[offset..]`.
+    fn shift_spans(node: &mut Node<'_>, offset: usize) {
+        node.span = Span::new(node.span.start - offset, node.span.end - offset);
+        if let Some(annotation) = &mut node.annotation {
+            shift_spans(annotation, offset);
+        }
+        match &mut node.kind {
+            Kind::List(items) | Kind::Vector(items) | Kind::Set(items) => {
+                items.iter_mut().for_each(|item| shift_spans(item, offset));
+            }
+            Kind::Map(entries) => {
+                for (k, v) in entries {
+                    shift_spans(k, offset);
+                    shift_spans(v, offset);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn assert_raw_reparses(node: &Node<'_>, src: &str) {
+        let raw = node.raw(src);
+        let mut reparsed = parse(raw).unwrap_or_else(|e| panic!("{raw:?}: {e}"));
+        assert_eq!(reparsed.len(), 1, "{raw:?}");
+        let mut expected = node.clone();
+        shift_spans(&mut expected, node.span.start);
+        assert_eq!(reparsed.pop().unwrap(), expected, "{raw:?}");
+
+        if let Some(annotation) = &node.annotation {
+            match &annotation.kind {
+                // Stacked annotations are merged into a list with no source text of its own.
+                Kind::List(items) if annotation.merged_annotations => {
+                    items.iter().for_each(|item| assert_raw_reparses(item, src));
+                }
+                _ => assert_raw_reparses(annotation, src),
+            }
+        }
+        match &node.kind {
+            Kind::List(items) | Kind::Vector(items) | Kind::Set(items) => {
+                items.iter().for_each(|item| assert_raw_reparses(item, src));
+            }
+            Kind::Map(entries) => {
+                for (k, v) in entries {
+                    assert_raw_reparses(k, src);
+                    assert_raw_reparses(v, src);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn every_node_raw_slice_reparses_to_an_equal_node() {
+        let input = "\u{feff}(defn #int sum [#int a, b] (+ a b)) ; comment\n\
+                     #a #b {:k \"v\\n\" `odd key`: \\space} ## skipped [a b]\n\
+                     %{1 -2.5M 3N} #(vec int) [## gone nil true \\u00e9 x:] #(a) #b #c y";
+        for node in parse(input).unwrap() {
+            assert_raw_reparses(&node, input);
+        }
+    }
+
+    #[test]
+    fn stacked_annotations_merge_in_source_order() {
+        let input = "#a #b x";
+        let node = &parse(input).unwrap()[0];
+        let annotation = node.annotation.as_deref().unwrap();
+        let Kind::List(items) = &annotation.kind else {
+            panic!("expected List, got: {:?}", annotation.kind);
+        };
+        assert_symbol(&items[0], "a");
+        assert_symbol(&items[1], "b");
+        assert_eq!(annotation.raw(input), "a #b");
+
+        let input = "#a #b #c x";
+        let annotation = parse(input).unwrap()[0].annotation.clone().unwrap();
+        let Kind::List(items) = &annotation.kind else {
+            panic!("expected List, got: {:?}", annotation.kind);
+        };
+        assert_eq!(items.len(), 3);
+        assert_symbol(&items[0], "a");
+        assert_symbol(&items[1], "b");
+        assert_symbol(&items[2], "c");
+        assert_eq!(annotation.raw(input), "a #b #c");
+
+        // A list written as an annotation stays one item of the merged list.
+        let input = "#a #(b c) x";
+        let annotation = parse(input).unwrap()[0].annotation.clone().unwrap();
+        assert!(annotation.merged_annotations);
+        let Kind::List(items) = &annotation.kind else {
+            panic!("expected List, got: {:?}", annotation.kind);
+        };
+        assert_eq!(items.len(), 2);
+        assert!(!items[1].merged_annotations);
+        assert_eq!(items[1].raw(input), "(b c)");
+    }
+
+    #[test]
+    fn collection_element_limit() {
+        let config = ParserConfig {
+            max_collection_len: Some(3),
+            ..ParserConfig::default()
+        };
+        assert!(Parser::with_config("[1 2 3] (a b c) %{1 2 3}", config)
+            .parse_all()
+            .is_ok());
+
+        for input in ["[1 2 3 4]", "(a b c d)", "%{1 2 3 4}", "{:a 1 :b 2}"] {
+            let err = Parser::with_config(input, config).parse_all().unwrap_err();
+            assert_eq!(err.kind, ErrorKind::LimitExceeded { limit: 3 }, "{input}");
+        }
+    }
+
+    #[test]
+    fn total_node_limit() {
+        let config = ParserConfig {
+            max_nodes: Some(5),
+            ..ParserConfig::default()
+        };
+        // `(a [b c])` is 5 nodes.
+        assert_eq!(
+            Parser::with_config("(a [b c])", config)
+                .parse_all()
+                .unwrap()
+                .len(),
+            1
+        );
+        let err = Parser::with_config("(a [b c]) d", config)
+            .parse_all()
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::LimitExceeded { limit: 5 });
+        assert_eq!(err.span, Span::new(10, 11));
+
+        // Annotations count as nodes too.
+        let err = Parser::with_config("#int (a b c d)", config)
+            .parse_all()
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::LimitExceeded { limit: 5 });
+    }
+
+    #[test]
+    fn nesting_depth_limit() {
+        let config = ParserConfig {
+            max_depth: Some(3),
+            ..ParserConfig::default()
+        };
+        assert!(Parser::with_config("[[(x)]] #a #b #c y", config)
+            .parse_all()
+            .is_ok());
+        for input in ["[[[[x]]]]", "{:a [%{(1)}]}", "#a #b #c #d y", "#a [[[x]]]"] {
+            let err = Parser::with_config(input, config).parse_all().unwrap_err();
+            assert_eq!(err.kind, ErrorKind::NestingTooDeep { limit: 3 }, "{input}");
+        }
+    }
+
+    #[test]
+    fn long_dispatch_chain_hits_untrusted_depth_limit() {
+        let input = "#a ".repeat(100_000) + "x";
+        let err = Parser::with_config(&input, ParserConfig::untrusted())
+            .parse_all()
+            .unwrap_err();
+        assert_eq!(
+            err.kind,
+            ErrorKind::NestingTooDeep {
+                limit: DEFAULT_MAX_DEPTH
+            }
+        );
+
+        let input = "[".repeat(DEFAULT_MAX_DEPTH) + &"]".repeat(DEFAULT_MAX_DEPTH);
+        assert!(Parser::with_config(&input, ParserConfig::untrusted())
+            .parse_all()
+            .is_ok());
+    }
+
+    #[test]
+    fn trailing_dispatch_fails_cleanly() {
+        assert_eq!(parse("x #").unwrap_err().kind, ErrorKind::InvalidDispatch);
+        assert_eq!(parse("x #a").unwrap_err().kind, ErrorKind::UnexpectedEof);
+        assert_eq!(parse("x ##").unwrap_err().kind, ErrorKind::UnexpectedEof);
+        assert_eq!(
+            parse("x #a #").unwrap_err().kind,
+            ErrorKind::InvalidDispatch
+        );
+    }
+
+    #[test]
+    fn leading_bom_is_skipped() {
+        let input = "\u{feff}(a 1)";
+        let values = parse(input).unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].span, Span::new(3, input.len()));
+
+        let values = parse_bytes(input.as_bytes()).unwrap();
+        let Kind::List(items) = &values[0].kind else {
+            panic!("expected List, got: {:?}", values[0].kind);
+        };
+        assert_symbol(&items[0], "a");
+    }
+
+    #[test]
+    fn invalid_utf8_bytes_rejected() {
+        let err = parse_bytes(b"(a)\n(b \xff c)").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidUtf8);
+        assert_eq!(err.span, Span::new(7, 8));
+        assert_eq!((err.line, err.column), (2, 4));
+    }
+
     #[test]
     fn strict_invalid_keyword_rejected() {
         assert!(parse("::foo").is_err());
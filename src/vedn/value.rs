@@ -20,6 +20,9 @@ pub struct Node<'a> {
     pub annotation: Option<Box<Node<'a>>>,
     /// The element kind.
     pub kind: Kind<'a>,
+    /// Whether this node is the list that stacked annotations (`#a #b x`) were merged into, as
+    /// opposed to a list written in the source (`#(a b) x`).
+    pub merged_annotations: bool,
 }
 
 impl<'a> Node<'a> {
@@ -29,8 +32,19 @@ impl<'a> Node<'a> {
             span,
             annotation: None,
             kind,
+            merged_annotations: false,
         }
     }
+
+    /// Returns the exact source text of this node, including a leading `#<annotation>`.
+    ///
+    /// `src` must be the input the node was parsed from, since `span` indexes into it. Every node
+    /// read from source re-parses from its raw slice, except the list that stacked annotations
+    /// (`#a #b x`) are merged into (see [`Node::merged_annotations`]): its slice runs from the
+    /// first annotation to the last.
+    pub fn raw<'s>(&self, src: &'s str) -> &'s str {
+        &src[self.span.start..self.span.end]
+    }
 }
 
 /// EDN value kinds.
@@ -180,6 +194,38 @@ impl<'a> Number<'a> {
         }
     }
 
+    /// Returns `true` for integer literals.
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Number::Int { .. })
+    }
+
+    /// Returns `true` if the literal denotes zero (e.g. `0`, `-0`, `0.0`, `0e10`).
+    ///
+    /// Like the other sign accessors, this only inspects the lexeme.
+    pub fn is_zero(&self) -> bool {
+        let core = self.lexeme().trim_end_matches(['N', 'M']);
+        let mantissa = core.split(['e', 'E']).next().unwrap_or(core);
+        mantissa
+            .bytes()
+            .all(|b| matches!(b, b'0' | b'.' | b'+' | b'-'))
+    }
+
+    /// Returns `true` if the literal denotes a value below zero. `-0` is not negative.
+    pub fn is_negative(&self) -> bool {
+        self.lexeme().starts_with('-') && !self.is_zero()
+    }
+
+    /// Returns `-1`, `0` or `1` according to the sign of the literal.
+    pub fn sign(&self) -> i8 {
+        if self.is_zero() {
+            0
+        } else if self.is_negative() {
+            -1
+        } else {
+            1
+        }
+    }
+
     /// Returns the lexeme as a borrowed [`Cow<str>`].
     pub fn as_cow_str(&self) -> Cow<'a, str> {
         Cow::Borrowed(self.lexeme())
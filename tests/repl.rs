@@ -0,0 +1,45 @@
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use tempfile::tempdir;
+
+#[test]
+fn repl_loads_init_files_and_survives_their_errors() {
+    let home = tempdir().unwrap();
+    let config = home.path().join(".config").join("vaca");
+    fs::create_dir_all(&config).unwrap();
+    fs::write(config.join("init.vaca"), "(defn greet [] \"hello\")\n").unwrap();
+
+    let project = tempdir().unwrap();
+    fs::write(
+        project.path().join(".vacarc"),
+        "(def n 1)\n(undefined-fn)\n",
+    )
+    .unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_vaca"))
+        .current_dir(project.path())
+        .env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run vaca binary");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"(greet)\n(+ n 1)\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success());
+    assert!(stdout.contains("\"hello\""), "{stdout}");
+    assert!(stdout.contains('2'), "{stdout}");
+    assert!(stderr.contains(".vacarc"), "{stderr}");
+    assert!(stderr.contains("undefined-fn"), "{stderr}");
+}
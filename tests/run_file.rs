@@ -0,0 +1,64 @@
+use std::fs;
+use std::process::Command;
+
+use tempfile::tempdir;
+
+fn run_vaca_file(src: impl AsRef<[u8]>) -> (bool, String) {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("main.vaca");
+    fs::write(&path, src).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_vaca"))
+        .arg(&path)
+        .output()
+        .expect("run vaca binary");
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn runtime_error_reports_failing_form_with_caret() {
+    let (ok, stderr) = run_vaca_file("(def x 1)\n  (+ x \"a\")\n(def y 2)\n");
+    assert!(!ok);
+    assert!(stderr.contains("error: type error: expected number, got string"));
+    assert!(stderr.contains("main.vaca:2:3"));
+    assert!(stderr.contains("2 |   (+ x \"a\")"));
+    assert!(stderr.lines().any(|l| l == "  |   ^^^^^^^^^"));
+}
+
+#[test]
+fn parse_error_reports_source_line() {
+    let (ok, stderr) = run_vaca_file("(def x 1)\n(def y ::bad)\n");
+    assert!(!ok);
+    assert!(stderr.contains("error: invalid keyword"));
+    assert!(stderr.contains("2 | (def y ::bad)"));
+    assert!(stderr.lines().any(|l| l == "  |        ^^^^^"));
+}
+
+#[test]
+fn invalid_utf8_file_reports_clean_error() {
+    let (ok, stderr) = run_vaca_file(b"\xef\xbb\xbf(def x 1)\n(def y \"\xff\")\n");
+    assert!(!ok);
+    assert!(stderr.contains("error: input is not valid UTF-8"));
+    assert!(stderr.contains("main.vaca:2:9"));
+}
+
+#[test]
+fn parse_error_in_used_module_points_into_that_module() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("bad.vaca"), "(def a 1)\n(def b ::oops)\n").unwrap();
+    let path = dir.path().join("main.vaca");
+    fs::write(&path, "(use bad)\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_vaca"))
+        .arg(&path)
+        .output()
+        .expect("run vaca binary");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("error: invalid keyword"));
+    assert!(stderr.contains("bad.vaca:2:8"));
+    assert!(stderr.contains("2 | (def b ::oops)"));
+}